@@ -213,6 +213,88 @@ export function useSignals(): EffectStore {
   return store;
 }
 
+interface ClassComponentInstance {
+  forceUpdate?(): void;
+}
+interface ClassEffectStore {
+  store: EffectStore;
+  /** version of the store during the last render */
+  renderedVersion: number;
+  unsubscribe: (() => void) | undefined;
+}
+const classEffectStores = new WeakMap<ClassComponentInstance, ClassEffectStore>();
+
+/**
+ * @description `useSignals` counterpart for `render` of class components, since hooks can't be used there. Used by swc plugin, which also calls `trackClassSignals.m(this)` in `componentDidMount` and `trackClassSignals.u(this)` in `componentWillUnmount`. If s.f() is not called - reactivity will break
+ * @example
+ * ```tsx
+ * class Component extends React.Component {
+ *   componentDidMount() {
+ *     trackClassSignals.m(this)
+ *   }
+ *   componentWillUnmount() {
+ *     trackClassSignals.u(this)
+ *   }
+ *   render() {
+ *     const s = trackClassSignals(this)
+ *     try {
+ *       return <p>{counter.value}</p>
+ *     } finally {
+ *       s.f()
+ *     }
+ *   }
+ * }
+ * ```
+ */
+export function trackClassSignals(
+  instance: ClassComponentInstance,
+): EffectStore {
+  let classStore = classEffectStores.get(instance);
+  if (classStore == null) {
+    classStore = {
+      store: createEffectStore(),
+      renderedVersion: 0,
+      unsubscribe: undefined,
+    };
+    classEffectStores.set(instance, classStore);
+  }
+
+  classStore.renderedVersion = classStore.store.getSnapshot();
+  classStore.store[EffectStoreFields.startTracking]();
+  return classStore.store;
+}
+
+/**
+ * Subscribes the instance on commit, so renders which are never committed (StrictMode,
+ * interrupted concurrent renders, Suspense) don't leave a subscription behind
+ */
+trackClassSignals.m = function (instance: ClassComponentInstance) {
+  const classStore = classEffectStores.get(instance);
+  if (classStore == null || classStore.unsubscribe) {
+    return;
+  }
+  const rerender = () => {
+    if (typeof instance.forceUpdate === "function") {
+      instance.forceUpdate();
+    }
+  };
+  classStore.unsubscribe = classStore.store.subscribe(rerender);
+  // signals changed between render and commit, or the instance is mounted again after
+  // unsubscribing (StrictMode), which disposes the effect until the next render
+  if (classStore.store.getSnapshot() !== classStore.renderedVersion) {
+    rerender();
+  }
+};
+
+/** Unsubscribes the instance, the effect is disposed until the next render */
+trackClassSignals.u = function (instance: ClassComponentInstance) {
+  const classStore = classEffectStores.get(instance);
+  if (classStore?.unsubscribe) {
+    classStore.unsubscribe();
+    classStore.unsubscribe = undefined;
+  }
+};
+
 /**
  * A wrapper component that renders a Signal's value directly as a Text node or JSX.
  */
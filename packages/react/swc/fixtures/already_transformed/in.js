@@ -0,0 +1,51 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { Component } from "react";
+
+const A = () => {
+  var _effect = _useSignals();
+  try {
+    return <div>{sig.value}</div>;
+  } finally {
+    _effect.f();
+  }
+};
+function useValue() {
+  var _effect = _useSignals();
+  try {
+    return sig.value;
+  } finally {
+    _effect.f();
+  }
+}
+function B() {
+  "use memo";
+  using _effect = _useSignals(1);
+  return <div>{sig.value}</div>;
+}
+/** @useSignals */
+const b = () => {
+  _useSignals();
+  return sig.value;
+};
+class Counter extends Component {
+  render() {
+    var _effect = _trackClassSignals(this);
+    try {
+      return <div>{sig.value}</div>;
+    } finally {
+      _effect.f();
+    }
+  }
+  componentDidMount() {
+    _trackClassSignals.m(this);
+    super.componentDidMount?.();
+  }
+  componentWillUnmount() {
+    _trackClassSignals.u(this);
+    super.componentWillUnmount?.();
+  }
+}
+
+// new code reuses existing import
+const B = () => <div>{sig.value}</div>;
@@ -0,0 +1,58 @@
+import { signal } from "@preact/signals-react";
+import { useEffect } from "react";
+
+const count = signal(0);
+
+function useCount() {
+  return count.value;
+}
+
+// reads through another hook
+function useDouble() {
+  return useCount() * 2;
+}
+
+const formatCount = () => `${count.value}`;
+
+function Card() {
+  const n = useCount();
+  return <p>{n}</p>;
+}
+
+function Double() {
+  return <p>{useDouble()}</p>;
+}
+
+const Label = () => <span>{formatCount()}</span>;
+
+// recursion without signal reads
+function ping(n) {
+  return n > 0 ? pong(n - 1) : 0;
+}
+function pong(n) {
+  return ping(n);
+}
+
+function Cycle() {
+  return <p>{ping(3)}</p>;
+}
+
+// recursion reaching a signal read
+function even(n) {
+  return n === 0 ? count.value : odd(n - 1);
+}
+function odd(n) {
+  return even(n);
+}
+
+function CycleRead() {
+  return <p>{odd(3)}</p>;
+}
+
+// called after render only
+function Effect() {
+  useEffect(() => {
+    formatCount();
+  }, []);
+  return <p />;
+}
@@ -0,0 +1,69 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-react";
+import { useEffect } from "react";
+const count = signal(0);
+function useCount() {
+    return count.value;
+}
+// reads through another hook
+function useDouble() {
+    return useCount() * 2;
+}
+const formatCount = ()=>`${count.value}`;
+function Card() {
+    var _effect = _useSignals();
+    try {
+        const n = useCount();
+        return <p>{n}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+function Double() {
+    var _effect = _useSignals();
+    try {
+        return <p>{useDouble()}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+const Label = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <span>{formatCount()}</span>;
+    } finally{
+        _effect.f();
+    }
+};
+// recursion without signal reads
+function ping(n) {
+    return n > 0 ? pong(n - 1) : 0;
+}
+function pong(n) {
+    return ping(n);
+}
+function Cycle() {
+    return <p>{ping(3)}</p>;
+}
+// recursion reaching a signal read
+function even(n) {
+    return n === 0 ? count.value : odd(n - 1);
+}
+function odd(n) {
+    return even(n);
+}
+function CycleRead() {
+    var _effect = _useSignals();
+    try {
+        return <p>{odd(3)}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+// called after render only
+function Effect() {
+    useEffect(()=>{
+        formatCount();
+    }, []);
+    return <p/>;
+}
@@ -0,0 +1,23 @@
+import { signal } from "@preact/signals-react";
+
+const todos = [signal("a"), signal("b")];
+
+const TodoList = () => (
+  <ul>
+    {todos.map((todo) => (
+      <li>{todo.value}</li>
+    ))}
+  </ul>
+);
+
+const Filtered = ({ items }) => {
+  const done = items.filter(({ done }) => done.value);
+  return <p>{done.length}</p>;
+};
+
+// should not be transformed
+const Reset = () => (
+  <button onClick={() => todos.forEach((todo) => (todo.value = ""))}>
+    reset
+  </button>
+);
@@ -0,0 +1,29 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-react";
+const todos = [
+    signal("a"),
+    signal("b")
+];
+const TodoList = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <ul>
+    {todos.map((todo)=><li>{todo.value}</li>)}
+  </ul>;
+    } finally{
+        _effect.f();
+    }
+};
+const Filtered = ({ items })=>{
+    var _effect = _useSignals();
+    try {
+        const done = items.filter(({ done })=>done.value);
+        return <p>{done.length}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+// should not be transformed
+const Reset = ()=><button onClick={()=>todos.forEach((todo)=>todo.value = "")}>
+    reset
+  </button>;
@@ -0,0 +1,21 @@
+import { signal } from "@preact/signals-react";
+
+const count = signal(0);
+
+const Rows = () => {
+  const renderRow = () => <div>{count.value}</div>;
+  return renderRow();
+};
+
+function renderLabel(label) {
+  return <span>{label}</span>;
+}
+
+const Label = () => renderLabel(count.value);
+
+// should not be transformed
+const Clicker = () => {
+  const renderToast = () => <div>{count.value}</div>;
+  useEffect(() => show(renderToast()), []);
+  return null;
+};
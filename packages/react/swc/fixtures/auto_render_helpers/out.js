@@ -0,0 +1,29 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-react";
+const count = signal(0);
+const Rows = ()=>{
+    var _effect = _useSignals();
+    try {
+        const renderRow = ()=><div>{count.value}</div>;
+        return renderRow();
+    } finally{
+        _effect.f();
+    }
+};
+function renderLabel(label) {
+    return <span>{label}</span>;
+}
+const Label = ()=>{
+    var _effect = _useSignals();
+    try {
+        return renderLabel(count.value);
+    } finally{
+        _effect.f();
+    }
+};
+// should not be transformed
+const Clicker = ()=>{
+    const renderToast = ()=><div>{count.value}</div>;
+    useEffect(()=>show(renderToast()), []);
+    return null;
+};
@@ -0,0 +1,44 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-core";
+const count = signal(0);
+// should not be transformed, read happens in effect
+const useLogger = ()=>{
+    useEffect(()=>{
+        console.log(count.value);
+    }, []);
+};
+// should not be transformed, read happens in event handler
+const Button = ()=><button onClick={()=>console.log(count.value)}>log</button>;
+// should not be transformed, jsx and read are inside of returned render prop
+const RenderCount = ()=>{
+    const render = ()=><p>{count.value}</p>;
+    return render;
+};
+const List = ({ items })=>{
+    var _effect = _useSignals();
+    try {
+        return <ul>
+    {items.map((item)=><li key={item}>{count.value}</li>)}
+  </ul>;
+    } finally{
+        _effect.f();
+    }
+};
+const Memoized = ()=>{
+    var _effect = _useSignals();
+    try {
+        const doubled = useMemo(()=>count.value * 2, []);
+        return <p>{doubled}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const Immediate = ()=>{
+    var _effect = _useSignals();
+    try {
+        const value = (()=>count.value)();
+        return <p>{value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
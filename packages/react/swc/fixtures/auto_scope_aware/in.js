@@ -0,0 +1,48 @@
+import { signal, useSignal } from "@preact/signals-core";
+import * as core from "@preact-signals/safe-react";
+import { store } from "./store";
+
+const counter = signal(0);
+const doubled = core.computed(() => counter.value * 2);
+const alias = doubled;
+
+// should not be transformed
+const Input = ({ setText }) => (
+  <input onChange={(e) => setText(e.target.value)} />
+);
+
+// should not be transformed
+const RefReader = () => {
+  const el = useRef(null);
+  return <p>{el.current.value}</p>;
+};
+
+// should not be transformed
+const Plain = () => {
+  const [state] = useState({ value: 1 });
+  return <p>{state.value}</p>;
+};
+
+const Counter = () => <p>{counter.value}</p>;
+
+const Aliased = () => <p>{alias.value}</p>;
+
+const Local = () => {
+  const sig = useSignal(0);
+  return <p>{sig.value}</p>;
+};
+
+const FromProps = ({ sig }) => <p>{sig.value}</p>;
+
+const FromPropsMember = (props) => <p>{props.sig.value}</p>;
+
+// unknown origin, treated as signal by default
+const Imported = () => <p>{store.value}</p>;
+
+const useCounter = () => counter.value;
+
+// should not be transformed
+const useInput = () => {
+  const ref = useRef();
+  return ref.current.value;
+};
@@ -0,0 +1,84 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal, useSignal } from "@preact/signals-core";
+import * as core from "@preact-signals/safe-react";
+import { store } from "./store";
+const counter = signal(0);
+const doubled = core.computed(()=>counter.value * 2);
+const alias = doubled;
+// should not be transformed
+const Input = ({ setText })=><input onChange={(e)=>setText(e.target.value)}/>;
+// should not be transformed
+const RefReader = ()=>{
+    const el = useRef(null);
+    return <p>{el.current.value}</p>;
+};
+// should not be transformed
+const Plain = ()=>{
+    const [state] = useState({
+        value: 1
+    });
+    return <p>{state.value}</p>;
+};
+const Counter = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{counter.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const Aliased = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{alias.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const Local = ()=>{
+    var _effect = _useSignals();
+    try {
+        const sig = useSignal(0);
+        return <p>{sig.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const FromProps = ({ sig })=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{sig.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const FromPropsMember = (props)=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{props.sig.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+// unknown origin, treated as signal by default
+const Imported = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{store.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+const useCounter = ()=>{
+    var _effect = _useSignals();
+    try {
+        return counter.value;
+    } finally{
+        _effect.f();
+    }
+};
+// should not be transformed
+const useInput = ()=>{
+    const ref = useRef();
+    return ref.current.value;
+};
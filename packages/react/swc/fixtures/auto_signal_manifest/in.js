@@ -0,0 +1,26 @@
+import { useCartTotal, formatPrice } from "./modules/cart";
+import useDiscount, { useTotal, useSize } from "./modules/hooks";
+import { useExternal } from "external";
+
+function Total() {
+  const total = useCartTotal();
+  return <p>{formatPrice(total)}</p>;
+}
+
+function Discount() {
+  return <p>{useDiscount()}</p>;
+}
+
+// re-exported by the index module
+function Reexported() {
+  return <p>{useTotal() + useSize()}</p>;
+}
+
+function Price() {
+  return <p>{formatPrice(1)}</p>;
+}
+
+// not in the manifest
+function External() {
+  return <p>{useExternal()}</p>;
+}
@@ -0,0 +1,14 @@
+import { signal } from "@preact/signals-react";
+
+const items = signal([]);
+
+export function useCartTotal() {
+  return items.value.reduce((sum, it) => sum + it.price, 0);
+}
+
+const useCartSize = () => items.value.length;
+export { useCartSize as useSize };
+
+export function formatPrice(price) {
+  return `$${price}`;
+}
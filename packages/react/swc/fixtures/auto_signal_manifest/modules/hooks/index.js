@@ -0,0 +1,8 @@
+import { useCartTotal } from "../cart";
+
+export { useCartTotal as useTotal } from "../cart";
+export * from "../cart";
+
+export default function useDiscount() {
+  return useCartTotal() * 0.9;
+}
@@ -0,0 +1,37 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { useCartTotal, formatPrice } from "./modules/cart";
+import useDiscount, { useTotal, useSize } from "./modules/hooks";
+import { useExternal } from "external";
+function Total() {
+    var _effect = _useSignals();
+    try {
+        const total = useCartTotal();
+        return <p>{formatPrice(total)}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+function Discount() {
+    var _effect = _useSignals();
+    try {
+        return <p>{useDiscount()}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+// re-exported by the index module
+function Reexported() {
+    var _effect = _useSignals();
+    try {
+        return <p>{useTotal() + useSize()}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+function Price() {
+    return <p>{formatPrice(1)}</p>;
+}
+// not in the manifest
+function External() {
+    return <p>{useExternal()}</p>;
+}
@@ -0,0 +1,12 @@
+import { signal } from "@preact/signals-react";
+import { store } from "./store";
+
+const counter = signal(0);
+
+const Counter = () => <p>{counter.value}</p>;
+
+// should not be transformed
+const Imported = () => <p>{store.value}</p>;
+
+// should not be transformed
+const Global = () => <p>{window.value}</p>;
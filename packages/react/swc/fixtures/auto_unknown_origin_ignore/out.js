@@ -0,0 +1,16 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-react";
+import { store } from "./store";
+const counter = signal(0);
+const Counter = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{counter.value}</p>;
+    } finally{
+        _effect.f();
+    }
+};
+// should not be transformed
+const Imported = ()=><p>{store.value}</p>;
+// should not be transformed
+const Global = ()=><p>{window.value}</p>;
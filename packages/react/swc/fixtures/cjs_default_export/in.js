@@ -0,0 +1,15 @@
+"use strict";
+const { memo } = require("react");
+
+module.exports = () => <div>{sig.value}</div>;
+exports.default = memo(function () {
+  return <div>{sig.value}</div>;
+});
+module.exports.default = function () {
+  return <div>{sig.value}</div>;
+};
+// named functions keep their name
+module.exports = function helper() {
+  return <div>{sig.value}</div>;
+};
+exports.Other = () => <div>{sig.value}</div>;
@@ -0,0 +1,39 @@
+"use strict";
+var _useSignals = require("@preact-signals/safe-react/tracking").useSignals;
+const { memo } = require("react");
+module.exports = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+exports.default = memo(function() {
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+module.exports.default = function() {
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+// named functions keep their name
+module.exports = function helper() {
+    return <div>{sig.value}</div>;
+};
+exports.Other = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
@@ -0,0 +1,28 @@
+import { Component } from "react";
+
+class Page extends Component {
+  // called by `render`, not rendered as components
+  Header() {
+    return <h1>{title.value}</h1>;
+  }
+  Row = () => <div>{row.value}</div>;
+  #Footer() {
+    return <footer>{footer.value}</footer>;
+  }
+  static Sidebar() {
+    return <aside>{sidebar.value}</aside>;
+  }
+  render() {
+    class Ui {
+      static Item = () => <li>{item.value}</li>;
+    }
+    return (
+      <main>
+        {this.Header()}
+        {this.Row()}
+        {this.#Footer()}
+        <Ui.Item />
+      </main>
+    );
+  }
+}
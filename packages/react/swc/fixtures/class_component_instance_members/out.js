@@ -0,0 +1,52 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { Component } from "react";
+class Page extends Component {
+    // called by `render`, not rendered as components
+    Header() {
+        return <h1>{title.value}</h1>;
+    }
+    Row = ()=><div>{row.value}</div>;
+    #Footer() {
+        return <footer>{footer.value}</footer>;
+    }
+    static Sidebar() {
+        var _effect = _useSignals();
+        try {
+            return <aside>{sidebar.value}</aside>;
+        } finally{
+            _effect.f();
+        }
+    }
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            class Ui {
+                static Item = ()=>{
+                    var _effect = _useSignals();
+                    try {
+                        return <li>{item.value}</li>;
+                    } finally{
+                        _effect.f();
+                    }
+                };
+            }
+            return <main>
+        {this.Header()}
+        {this.Row()}
+        {this.#Footer()}
+        <Ui.Item/>
+      </main>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
@@ -0,0 +1,65 @@
+import React, { Component, PureComponent } from "react";
+
+class Counter extends React.Component {
+  render() {
+    return <p>{count.value}</p>;
+  }
+}
+
+class Pure extends PureComponent {
+  render() {
+    return <p>{count.value}</p>;
+  }
+}
+
+// lowercase classes extending Component are components too
+const counter = class extends Component {
+  render() {
+    return <p>{count.value}</p>;
+  }
+};
+
+// name and jsx in render are enough
+class Widget {
+  render() {
+    return <div />;
+  }
+}
+
+// should not be transformed
+class Model {
+  render() {
+    return "model";
+  }
+}
+
+// should not be transformed
+class helper {
+  render() {
+    return <div />;
+  }
+}
+
+/**
+ * @noUseSignals
+ */
+export class OptOut extends Component {
+  render() {
+    return <p>{count.value}</p>;
+  }
+}
+
+export class Exported extends Component {
+  /**
+   * @noUseSignals
+   */
+  render() {
+    return <p>{count.value}</p>;
+  }
+}
+
+export default class extends Component {
+  render() {
+    return <p>{count.value}</p>;
+  }
+}
@@ -0,0 +1,118 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import React, { Component, PureComponent } from "react";
+class Counter extends React.Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
+class Pure extends PureComponent {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
+// lowercase classes extending Component are components too
+const counter = class extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+};
+// name and jsx in render are enough
+class Widget {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <div/>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+    }
+}
+// should not be transformed
+class Model {
+    render() {
+        return "model";
+    }
+}
+// should not be transformed
+class helper {
+    render() {
+        return <div/>;
+    }
+}
+/**
+ * @noUseSignals
+ */ export class OptOut extends Component {
+    render() {
+        return <p>{count.value}</p>;
+    }
+}
+export class Exported extends Component {
+    /**
+   * @noUseSignals
+   */ render() {
+        return <p>{count.value}</p>;
+    }
+}
+export default class extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
@@ -0,0 +1,21 @@
+import { Component } from "react";
+import { signal } from "@preact/signals-react";
+
+const count = signal(0);
+
+class Counter extends Component {
+  handleClick = () => {
+    count.value++;
+  };
+
+  render() {
+    return <button onClick={this.handleClick}>{count.value}</button>;
+  }
+}
+
+// should not be transformed
+class Input extends Component {
+  render() {
+    return <input onChange={(e) => this.setState({ text: e.target.value })} />;
+  }
+}
@@ -0,0 +1,33 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { Component } from "react";
+import { signal } from "@preact/signals-react";
+const count = signal(0);
+class Counter extends Component {
+    handleClick = ()=>{
+        count.value++;
+    };
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <button onClick={this.handleClick}>{count.value}</button>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
+// should not be transformed
+class Input extends Component {
+    render() {
+        return <input onChange={(e)=>this.setState({
+                text: e.target.value
+            })}/>;
+    }
+}
@@ -0,0 +1,21 @@
+import { Component } from "react";
+
+class Clock extends Component {
+  componentDidMount() {
+    this.timer = setInterval(tick, 1000);
+  }
+  componentWillUnmount = () => clearInterval(this.timer);
+  render() {
+    return <p>{time.value}</p>;
+  }
+}
+
+class Logger extends Component {
+  componentDidMount = function () {
+    log("mounted");
+  };
+  componentWillUnmount = cleanup;
+  render() {
+    return <p>{message.value}</p>;
+  }
+}
@@ -0,0 +1,35 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { Component } from "react";
+class Clock extends Component {
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        this.timer = setInterval(tick, 1000);
+    }
+    componentWillUnmount = ()=>{
+        _trackClassSignals.u(this);
+        return clearInterval(this.timer);
+    };
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{time.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+}
+class Logger extends Component {
+    componentDidMount = function() {
+        _trackClassSignals.m(this);
+        log("mounted");
+    };
+    componentWillUnmount = cleanup;
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{message.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+}
@@ -0,0 +1,37 @@
+import { Component, memo } from "react";
+
+const Memoized = memo(
+  class extends Component {
+    render() {
+      return <p>{count.value}</p>;
+    }
+  },
+);
+
+const Observed = observer(
+  class Observed extends Component {
+    render() {
+      return <p>{count.value}</p>;
+    }
+  },
+);
+
+ui.Header = class Header extends Component {
+  render() {
+    return <h1>{title.value}</h1>;
+  }
+};
+
+Footer = class {
+  render() {
+    return <footer>{footer.value}</footer>;
+  }
+};
+
+export default memo(
+  class extends Component {
+    render() {
+      return <main>{page.value}</main>;
+    }
+  },
+);
@@ -0,0 +1,90 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { Component, memo } from "react";
+const Memoized = memo(class extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+});
+const Observed = observer(class Observed extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <p>{count.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+});
+ui.Header = class Header extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <h1>{title.value}</h1>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+};
+Footer = class {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <footer>{footer.value}</footer>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+    }
+};
+export default memo(class extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <main>{page.value}</main>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+});
@@ -0,0 +1,32 @@
+import { memo } from "react";
+
+class Ui {
+  static Header = () => <h1>{title.value}</h1>;
+  Row = memo(() => <div>{row.value}</div>);
+  #Private = () => <p>{text.value}</p>;
+  static Footer() {
+    return <footer>{footer.value}</footer>;
+  }
+  static useTitle() {
+    return title.value;
+  }
+  /** @noUseSignals */
+  static Skipped = () => <h2>{title.value}</h2>;
+  /** @useSignals */
+  static format = () => title.value;
+  // not components
+  static helper = () => title.value;
+  handleClick = () => {
+    counter.value++;
+  };
+  get Getter() {
+    return <div>{title.value}</div>;
+  }
+}
+
+class Counter extends Component {
+  static Label = () => <span>{label.value}</span>;
+  render() {
+    return <div>{counter.value}</div>;
+  }
+}
@@ -0,0 +1,88 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { memo } from "react";
+class Ui {
+    static Header = ()=>{
+        var _effect = _useSignals();
+        try {
+            return <h1>{title.value}</h1>;
+        } finally{
+            _effect.f();
+        }
+    };
+    Row = memo(()=>{
+        var _effect = _useSignals();
+        try {
+            return <div>{row.value}</div>;
+        } finally{
+            _effect.f();
+        }
+    });
+    #Private = ()=>{
+        var _effect = _useSignals();
+        try {
+            return <p>{text.value}</p>;
+        } finally{
+            _effect.f();
+        }
+    };
+    static Footer() {
+        var _effect = _useSignals();
+        try {
+            return <footer>{footer.value}</footer>;
+        } finally{
+            _effect.f();
+        }
+    }
+    static useTitle() {
+        var _effect = _useSignals();
+        try {
+            return title.value;
+        } finally{
+            _effect.f();
+        }
+    }
+    /** @noUseSignals */ static Skipped = ()=><h2>{title.value}</h2>;
+    /** @useSignals */ static format = ()=>{
+        var _effect = _useSignals();
+        try {
+            return title.value;
+        } finally{
+            _effect.f();
+        }
+    };
+    // not components
+    static helper = ()=>title.value;
+    handleClick = ()=>{
+        counter.value++;
+    };
+    get Getter() {
+        return <div>{title.value}</div>;
+    }
+}
+class Counter extends Component {
+    static Label = ()=>{
+        var _effect = _useSignals();
+        try {
+            return <span>{label.value}</span>;
+        } finally{
+            _effect.f();
+        }
+    };
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <div>{counter.value}</div>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
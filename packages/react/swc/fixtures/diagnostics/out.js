@@ -0,0 +1,24 @@
+/** @useSignals */ /** @noUseSignals */ const Conflict = ()=><div>{sig.value}</div>;
+// @useSignals
+const BodyConflict = ()=>{
+    "use no signals";
+    return <div>{sig.value}</div>;
+};
+/** @useSignals */ const value = sig.value;
+const obj = {
+    // @useSignals
+    get Getter () {
+        return <div>{sig.value}</div>;
+    }
+};
+/** @useSignals */ const fetchValue = async ()=>{
+    return sig.value;
+};
+// @useSignals
+function* values() {
+    yield sig.value;
+}
+// async components aren't tracked without a warning
+async function Async() {
+    return <div>{sig.value}</div>;
+}
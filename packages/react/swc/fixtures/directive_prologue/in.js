@@ -0,0 +1,27 @@
+function A() {
+  "use strict";
+  "use memo";
+  return <div>{sig.value}</div>;
+}
+const B = () => {
+  "use no memo";
+  return <div>{sig.value}</div>;
+};
+const useValue = () => {
+  "use signals";
+  return sig.value;
+};
+// worklets run on the UI thread
+const Worklet = () => {
+  "worklet";
+  return <div>{sig.value}</div>;
+};
+function useWorklet() {
+  "worklet";
+  return sig.value;
+}
+/** @useSignals */
+const onFrame = () => {
+  "worklet";
+  return sig.value;
+};
@@ -0,0 +1,42 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+function A() {
+    "use strict";
+    "use memo";
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
+const B = ()=>{
+    "use no memo";
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const useValue = ()=>{
+    "use signals";
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+};
+// worklets run on the UI thread
+const Worklet = ()=>{
+    "worklet";
+    return <div>{sig.value}</div>;
+};
+function useWorklet() {
+    "worklet";
+    return sig.value;
+}
+/** @useSignals */ const onFrame = ()=>{
+    "worklet";
+    return sig.value;
+};
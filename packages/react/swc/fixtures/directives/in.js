@@ -0,0 +1,53 @@
+// @useSignals
+const lineOptIn = () => {
+  return sig.value;
+};
+
+// @noUseSignals
+const LineOptOut = () => <div>{sig.value}</div>;
+
+/**
+ * Renders the counter.
+ *
+ * @useSignals
+ */
+function jsDocOptIn() {
+  return sig.value;
+}
+
+/**
+ * @noUseSignals counter is rendered by a parent
+ */
+function JsDocOptOut() {
+  return <div>{sig.value}</div>;
+}
+
+const bodyOptIn = () => {
+  "use signals";
+  return sig.value;
+};
+
+function BodyOptOut() {
+  "use no signals";
+  return <div>{sig.value}</div>;
+}
+
+const obj = {
+  // @useSignals
+  method() {
+    return sig.value;
+  },
+  Component() {
+    "use no signals";
+    return <div>{sig.value}</div>;
+  },
+};
+
+// not directives
+const notDirective = () => {
+  foo();
+  "use signals";
+  return sig.value;
+};
+// @useSignalsLater
+const notTag = () => sig.value;
@@ -0,0 +1,65 @@
+// @useSignals
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const lineOptIn = ()=>{
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+};
+// @noUseSignals
+const LineOptOut = ()=><div>{sig.value}</div>;
+/**
+ * Renders the counter.
+ *
+ * @useSignals
+ */ function jsDocOptIn() {
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+/**
+ * @noUseSignals counter is rendered by a parent
+ */ function JsDocOptOut() {
+    return <div>{sig.value}</div>;
+}
+const bodyOptIn = ()=>{
+    "use signals";
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+};
+function BodyOptOut() {
+    "use no signals";
+    return <div>{sig.value}</div>;
+}
+const obj = {
+    // @useSignals
+    method () {
+        var _effect = _useSignals();
+        try {
+            return sig.value;
+        } finally{
+            _effect.f();
+        }
+    },
+    Component () {
+        "use no signals";
+        return <div>{sig.value}</div>;
+    }
+};
+// not directives
+const notDirective = ()=>{
+    foo();
+    "use signals";
+    return sig.value;
+};
+// @useSignalsLater
+const notTag = ()=>sig.value;
@@ -0,0 +1,8 @@
+/**
+ * @signalsMode all
+ */
+import { signal } from "@preact/signals-core";
+
+const sig = signal(0);
+const A = () => <div>{sig.value}</div>;
+const useValue = () => sig.value;
@@ -0,0 +1,21 @@
+/**
+ * @signalsMode all
+ */ import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal } from "@preact/signals-core";
+const sig = signal(0);
+const A = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const useValue = ()=>{
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+};
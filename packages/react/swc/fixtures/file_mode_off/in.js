@@ -0,0 +1,8 @@
+// legacy code, tracked with withTrackSignals
+// @signalsMode off
+const A = () => <div>{sig.value}</div>;
+
+/** @useSignals */
+function b() {
+  return sig.value;
+}
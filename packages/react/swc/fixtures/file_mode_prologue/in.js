@@ -0,0 +1,7 @@
+"use client";
+"use signals auto";
+
+const A = () => <div>{sig.value}</div>;
+// doesn't read signals
+const B = () => <div>{ref.current.value}</div>;
+const ref = useRef();
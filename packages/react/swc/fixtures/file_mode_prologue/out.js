@@ -0,0 +1,14 @@
+"use client";
+"use signals auto";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const A = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+// doesn't read signals
+const B = ()=><div>{ref.current.value}</div>;
+const ref = useRef();
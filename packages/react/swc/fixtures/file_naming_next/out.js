@@ -0,0 +1,9 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+export default function() {
+    var _effect = _useSignals();
+    try {
+        return <main>{sig.value}</main>;
+    } finally{
+        _effect.f();
+    }
+}
@@ -0,0 +1 @@
+export default () => <div>{sig.value}</div>;
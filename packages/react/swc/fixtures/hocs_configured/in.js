@@ -0,0 +1,16 @@
+const Memo = memo(() => <div>{a.value}</div>);
+const ReactMemo = React.memo(function () {
+  return <div>{a.value}</div>;
+});
+// curried
+const Connected = connect(mapState)(() => <div>{a.value}</div>);
+// component isn't the first argument
+const Routed = withRouter({ exact: true }, () => <div>{a.value}</div>);
+const StyledCall = styled(() => <div>{a.value}</div>);
+const Nested = memo(connect(mapState)(() => <div>{a.value}</div>));
+
+// not hocs
+const Render = useCallback(() => <div>{a.value}</div>, []);
+const Timer = setTimeout(() => <div>{a.value}</div>, 100);
+const Value = useMemo(() => <div>{a.value}</div>, []);
+const Spread = withRouter(...args, () => <div>{a.value}</div>);
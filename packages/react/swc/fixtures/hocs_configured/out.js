@@ -0,0 +1,58 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const Memo = memo(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+const ReactMemo = React.memo(function() {
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+// curried
+const Connected = connect(mapState)(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+// component isn't the first argument
+const Routed = withRouter({
+    exact: true
+}, ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+const StyledCall = styled(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+const Nested = memo(connect(mapState)(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}));
+// not hocs
+const Render = useCallback(()=><div>{a.value}</div>, []);
+const Timer = setTimeout(()=><div>{a.value}</div>, 100);
+const Value = useMemo(()=><div>{a.value}</div>, []);
+const Spread = withRouter(...args, ()=><div>{a.value}</div>);
@@ -0,0 +1,7 @@
+const Memo = memo(() => <div>{a.value}</div>);
+const Ref = React.forwardRef((props, ref) => <div ref={ref}>{a.value}</div>);
+const Both = React.memo(forwardRef((props, ref) => <div ref={ref}>{a.value}</div>));
+
+// not hocs
+const Render = useCallback(() => <div>{a.value}</div>, []);
+const Value = useMemo(() => <div>{a.value}</div>, []);
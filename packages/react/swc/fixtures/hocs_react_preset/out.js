@@ -0,0 +1,28 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const Memo = memo(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+const Ref = React.forwardRef((props, ref)=>{
+    var _effect = _useSignals();
+    try {
+        return <div ref={ref}>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+});
+const Both = React.memo(forwardRef((props, ref)=>{
+    var _effect = _useSignals();
+    try {
+        return <div ref={ref}>{a.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}));
+// not hocs
+const Render = useCallback(()=><div>{a.value}</div>, []);
+const Value = useMemo(()=><div>{a.value}</div>, []);
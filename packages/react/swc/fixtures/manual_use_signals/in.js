@@ -0,0 +1,27 @@
+import { useSignals as track } from "@preact/signals-react/runtime";
+
+const A = () => {
+  const store = track();
+  try {
+    return <div>{sig.value}</div>;
+  } finally {
+    store.f();
+  }
+};
+function B() {
+  track();
+  return <div>{sig.value}</div>;
+}
+// @noUseSignals
+const C = () => {
+  track();
+  return <div>{sig.value}</div>;
+};
+// nested components are checked separately
+const D = () => {
+  const E = () => {
+    track();
+    return <div>{sig.value}</div>;
+  };
+  return <E />;
+};
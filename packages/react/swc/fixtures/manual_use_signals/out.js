@@ -0,0 +1,32 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as track } from "@preact/signals-react/runtime";
+const A = ()=>{
+    const store = track();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        store.f();
+    }
+};
+function B() {
+    track();
+    return <div>{sig.value}</div>;
+}
+// @noUseSignals
+const C = ()=>{
+    track();
+    return <div>{sig.value}</div>;
+};
+// nested components are checked separately
+const D = ()=>{
+    var _effect = _useSignals();
+    try {
+        const E = ()=>{
+            track();
+            return <div>{sig.value}</div>;
+        };
+        return <E/>;
+    } finally{
+        _effect.f();
+    }
+};
@@ -0,0 +1,19 @@
+import { useSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as useRuntimeSignals } from "@preact/signals-react/runtime";
+
+const A = () => {
+  const store = useSignals();
+  try {
+    return <div>{sig.value}</div>;
+  } finally {
+    store.f();
+  }
+};
+function B() {
+  useSignals();
+  return <div>{sig.value}</div>;
+}
+function C() {
+  useRuntimeSignals();
+  return <div>{sig.value}</div>;
+}
@@ -0,0 +1,25 @@
+import { useSignals } from "@preact-signals/safe-react/tracking";
+const A = ()=>{
+    var _effect = useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+function B() {
+    var _effect = useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
+function C() {
+    var _effect = useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
@@ -0,0 +1,26 @@
+import { useSignals } from "@preact/signals-react/runtime";
+
+const A = () => {
+  const store = useSignals();
+  try {
+    return <div>{sig.value}</div>;
+  } finally {
+    store.f();
+  }
+};
+function B() {
+  useSignals();
+  return <div>{sig.value}</div>;
+}
+// @useSignals
+function useValue() {
+  "use memo";
+  useSignals();
+  return sig.value;
+}
+// can't be rewritten
+const C = () => {
+  const store = useSignals();
+  useEffect(() => () => store.f());
+  return <div>{sig.value}</div>;
+};
@@ -0,0 +1,33 @@
+import { useSignals } from "@preact/signals-react/runtime";
+const A = ()=>{
+    var _effect = useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+function B() {
+    var _effect = useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
+// @useSignals
+function useValue() {
+    "use memo";
+    var _effect = useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+// can't be rewritten
+const C = ()=>{
+    const store = useSignals();
+    useEffect(()=>()=>store.f());
+    return <div>{sig.value}</div>;
+};
@@ -0,0 +1,9 @@
+const A = () => <div>{sig.value}</div>;
+const B = () => <div>static</div>;
+function useValue() {
+  return sig.value;
+}
+/** @useSignals */
+function useTracked() {
+  return sig.value;
+}
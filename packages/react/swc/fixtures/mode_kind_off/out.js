@@ -0,0 +1,16 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const A = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const B = ()=><div>static</div>;
+function useValue() {
+    return sig.value;
+}
+/** @useSignals */ function useTracked() {
+    return sig.value;
+}
@@ -0,0 +1,10 @@
+const A = () => <div>{sig.value}</div>;
+function useValue() {
+  return sig.value;
+}
+/** @useSignals */
+function useTracked() {
+  return sig.value;
+}
+/** @useSignals */
+const read = () => sig.value;
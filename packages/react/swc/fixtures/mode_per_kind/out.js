@@ -0,0 +1,28 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const A = ()=>{
+    var _effect = _useSignals(1);
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+function useValue() {
+    return sig.value;
+}
+/** @useSignals */ function useTracked() {
+    var _effect = _useSignals(2);
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+/** @useSignals */ const read = ()=>{
+    var _effect = _useSignals(2);
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+};
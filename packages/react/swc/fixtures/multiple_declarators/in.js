@@ -0,0 +1,23 @@
+const Header = () => <h1>{t.value}</h1>,
+  Footer = () => <p>{t.value}</p>,
+  value = 10;
+
+/**
+ * @noUseSignals
+ */
+const Ignored = () => <h1>{t.value}</h1>,
+  AlsoIgnored = () => <p>{t.value}</p>;
+
+/**
+ * @useSignals
+ */
+export const a = () => 10,
+  b = function () {
+    return 10;
+  };
+
+const c = () => 10,
+  /**
+   * @useSignals
+   */
+  d = () => 10;
@@ -0,0 +1,46 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const Header = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <h1>{t.value}</h1>;
+    } finally{
+        _effect.f();
+    }
+}, Footer = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <p>{t.value}</p>;
+    } finally{
+        _effect.f();
+    }
+}, value = 10;
+/**
+ * @noUseSignals
+ */ const Ignored = ()=><h1>{t.value}</h1>, AlsoIgnored = ()=><p>{t.value}</p>;
+/**
+ * @useSignals
+ */ export const a = ()=>{
+    var _effect = _useSignals();
+    try {
+        return 10;
+    } finally{
+        _effect.f();
+    }
+}, b = function() {
+    var _effect = _useSignals();
+    try {
+        return 10;
+    } finally{
+        _effect.f();
+    }
+};
+const c = ()=>10, /**
+   * @useSignals
+   */ d = ()=>{
+    var _effect = _useSignals();
+    try {
+        return 10;
+    } finally{
+        _effect.f();
+    }
+};
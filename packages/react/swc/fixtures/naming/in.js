@@ -0,0 +1,11 @@
+const _Row = () => <div>{sig.value}</div>;
+function use_query() {
+  return sig.value;
+}
+const row = () => <div>{sig.value}</div>;
+// tracked without reading signals
+const IconStar = ({ size }) => <svg width={size} />;
+const LegacyTable = () => <table>{sig.value}</table>;
+// explicit directive wins
+/** @useSignals */
+const LegacyList = () => <ul>{sig.value}</ul>;
@@ -0,0 +1,37 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+const _Row = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+function use_query() {
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+const row = ()=><div>{sig.value}</div>;
+// tracked without reading signals
+const IconStar = ({ size })=>{
+    var _effect = _useSignals();
+    try {
+        return <svg width={size}/>;
+    } finally{
+        _effect.f();
+    }
+};
+const LegacyTable = ()=><table>{sig.value}</table>;
+// explicit directive wins
+/** @useSignals */ const LegacyList = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <ul>{sig.value}</ul>;
+    } finally{
+        _effect.f();
+    }
+};
@@ -0,0 +1,39 @@
+import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+import Lib, { createElement } from "react";
+import * as Preact from "preact";
+
+function Automatic() {
+  return _jsxs("div", { children: [sig.value, _jsx("span", {})] });
+}
+
+function Development() {
+  return _jsxDEV("div", { children: sig.value }, void 0, false);
+}
+
+function Imported() {
+  return createElement("div", null, sig.value);
+}
+
+function DefaultImport() {
+  return Lib.createElement("div", null, sig.value);
+}
+
+// `React` doesn't have to be imported
+function Classic() {
+  return React.createElement("div", null, sig.value);
+}
+
+function Pragma() {
+  return h("div", null, sig.value);
+}
+
+function MemberPragma() {
+  return Preact.h("div", null, sig.value);
+}
+
+// not bound to a factory
+function NotJsx() {
+  const jsx = (value) => value;
+  return jsx(sig.value);
+}
@@ -0,0 +1,74 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+import Lib, { createElement } from "react";
+import * as Preact from "preact";
+function Automatic() {
+    var _effect = _useSignals();
+    try {
+        return _jsxs("div", {
+            children: [
+                sig.value,
+                _jsx("span", {})
+            ]
+        });
+    } finally{
+        _effect.f();
+    }
+}
+function Development() {
+    var _effect = _useSignals();
+    try {
+        return _jsxDEV("div", {
+            children: sig.value
+        }, void 0, false);
+    } finally{
+        _effect.f();
+    }
+}
+function Imported() {
+    var _effect = _useSignals();
+    try {
+        return createElement("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+function DefaultImport() {
+    var _effect = _useSignals();
+    try {
+        return Lib.createElement("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+// `React` doesn't have to be imported
+function Classic() {
+    var _effect = _useSignals();
+    try {
+        return React.createElement("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+function Pragma() {
+    var _effect = _useSignals();
+    try {
+        return h("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+function MemberPragma() {
+    var _effect = _useSignals();
+    try {
+        return Preact.h("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+// not bound to a factory
+function NotJsx() {
+    const jsx = (value)=>value;
+    return jsx(sig.value);
+}
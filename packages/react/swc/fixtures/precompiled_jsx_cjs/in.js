@@ -0,0 +1,22 @@
+"use strict";
+var _jsxRuntime = require("react/jsx-runtime");
+var _react = _interop_require_default(require("react"));
+const { jsx: _jsx } = require("react/jsx-runtime");
+
+function Automatic() {
+  return (0, _jsxRuntime.jsx)("div", { children: sig.value });
+}
+
+function Destructured() {
+  return _jsx("div", { children: sig.value });
+}
+
+function DefaultInterop() {
+  return _react.default.createElement("div", null, sig.value);
+}
+
+// elements are only created in a callback running after render
+function Later() {
+  const onClick = () => (0, _jsxRuntime.jsx)("div", {});
+  return sig.value + onClick;
+}
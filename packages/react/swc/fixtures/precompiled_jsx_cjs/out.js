@@ -0,0 +1,38 @@
+"use strict";
+var _useSignals = require("@preact-signals/safe-react/tracking").useSignals;
+var _jsxRuntime = require("react/jsx-runtime");
+var _react = _interop_require_default(require("react"));
+const { jsx: _jsx } = require("react/jsx-runtime");
+function Automatic() {
+    var _effect = _useSignals();
+    try {
+        return (0, _jsxRuntime.jsx)("div", {
+            children: sig.value
+        });
+    } finally{
+        _effect.f();
+    }
+}
+function Destructured() {
+    var _effect = _useSignals();
+    try {
+        return _jsx("div", {
+            children: sig.value
+        });
+    } finally{
+        _effect.f();
+    }
+}
+function DefaultInterop() {
+    var _effect = _useSignals();
+    try {
+        return _react.default.createElement("div", null, sig.value);
+    } finally{
+        _effect.f();
+    }
+}
+// elements are only created in a callback running after render
+function Later() {
+    const onClick = ()=>(0, _jsxRuntime.jsx)("div", {});
+    return sig.value + onClick;
+}
@@ -0,0 +1,10 @@
+export function Counter() {
+  return <p>{count.value}</p>;
+}
+
+// inline server actions are never tracked
+/** @useSignals */
+function Action() {
+  "use server";
+  return <p>{count.value}</p>;
+}
@@ -0,0 +1,14 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+export function Counter() {
+    var _effect = _useSignals();
+    try {
+        return <p>{count.value}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+// inline server actions are never tracked
+/** @useSignals */ function Action() {
+    "use server";
+    return <p>{count.value}</p>;
+}
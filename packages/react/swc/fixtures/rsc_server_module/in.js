@@ -0,0 +1,18 @@
+import { Inter } from "next/font/google";
+import "./globals.css";
+
+const inter = Inter({ subsets: ["latin"] });
+
+// server components are left as is, `@noUseSignals` isn't needed
+export default function RootLayout({ children }) {
+  return (
+    <html lang="en">
+      <body className={inter.className}>{children}</body>
+    </html>
+  );
+}
+
+/** @useSignals */
+export function Counter() {
+  return <p>{count.value}</p>;
+}
@@ -0,0 +1,5 @@
+"use client";
+
+export function Counter() {
+  return <p>{count.value}</p>;
+}
@@ -0,0 +1,10 @@
+"use client";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+export function Counter() {
+    var _effect = _useSignals();
+    try {
+        return <p>{count.value}</p>;
+    } finally{
+        _effect.f();
+    }
+}
@@ -0,0 +1,9 @@
+"use server";
+
+export async function increment() {
+  count.value++;
+}
+
+export function Form() {
+  return <form>{count.value}</form>;
+}
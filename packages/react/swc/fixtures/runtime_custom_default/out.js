@@ -0,0 +1,37 @@
+import { trackClass as _trackClassSignals } from "my-signals/runtime";
+import _useSignals from "my-signals/runtime";
+import { Component } from "react";
+function A() {
+    var _effect = _useSignals();
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
+function useValue() {
+    var _effect = _useSignals();
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+class B extends Component {
+    render() {
+        var _effect = _trackClassSignals(this);
+        try {
+            return <div>{sig.value}</div>;
+        } finally{
+            _effect.f();
+        }
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
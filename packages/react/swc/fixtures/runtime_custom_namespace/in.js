@@ -0,0 +1,13 @@
+import { Component } from "react";
+
+function A() {
+  return <div>{sig.value}</div>;
+}
+function useValue() {
+  return sig.value;
+}
+class B extends Component {
+  render() {
+    return <div>{sig.value}</div>;
+  }
+}
@@ -0,0 +1,36 @@
+import * as _signalsRuntime from "my-signals/runtime";
+import { Component } from "react";
+function A() {
+    var _effect = _signalsRuntime.track("component");
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.end();
+    }
+}
+function useValue() {
+    var _effect = _signalsRuntime.track("hook");
+    try {
+        return sig.value;
+    } finally{
+        _effect.end();
+    }
+}
+class B extends Component {
+    render() {
+        var _effect = _signalsRuntime.trackClass(this);
+        try {
+            return <div>{sig.value}</div>;
+        } finally{
+            _effect.end();
+        }
+    }
+    componentDidMount() {
+        _signalsRuntime.trackClass.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _signalsRuntime.trackClass.u(this);
+        super.componentWillUnmount?.();
+    }
+}
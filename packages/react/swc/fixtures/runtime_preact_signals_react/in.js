@@ -0,0 +1,19 @@
+import { Component } from "react";
+
+function A() {
+  return <div>{sig.value}</div>;
+}
+function useValue() {
+  return sig.value;
+}
+class B extends Component {
+  render() {
+    return <div>{sig.value}</div>;
+  }
+}
+// @useSignals
+class Label {
+  render() {
+    return sig.value;
+  }
+}
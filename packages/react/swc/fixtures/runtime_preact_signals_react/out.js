@@ -0,0 +1,29 @@
+import { useSignals as _useSignals } from "@preact/signals-react/runtime";
+import { Component } from "react";
+function A() {
+    var _effect = _useSignals(1);
+    try {
+        return <div>{sig.value}</div>;
+    } finally{
+        _effect.f();
+    }
+}
+function useValue() {
+    var _effect = _useSignals(2);
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+class B extends Component {
+    render() {
+        return <div>{sig.value}</div>;
+    }
+}
+// @useSignals
+class Label {
+    render() {
+        return sig.value;
+    }
+}
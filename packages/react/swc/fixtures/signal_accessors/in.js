@@ -0,0 +1,40 @@
+import { signal, untracked } from "@preact/signals-react";
+import { useEffect } from "react";
+
+const count = signal(0);
+const state = new Signal.State(0);
+
+function Peek() {
+  return <p>{count.peek()}</p>;
+}
+
+function Untracked() {
+  return <p>{untracked(() => count.value)}</p>;
+}
+
+function Method() {
+  return <p>{state.get()}</p>;
+}
+
+// `ReactiveRef` of `@preact-signals/utils`
+function Reactive({ reactiveRef }) {
+  return <p>{reactiveRef.v}</p>;
+}
+
+function Value() {
+  return <p>{count.value}</p>;
+}
+
+function useMethod() {
+  return state.get();
+}
+
+function usePeek() {
+  return count.peek();
+}
+
+// `.get()` in an effect isn't a render phase read
+function Effect() {
+  useEffect(() => console.log(state.get()), []);
+  return <p />;
+}
@@ -0,0 +1,52 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { signal, untracked } from "@preact/signals-react";
+import { useEffect } from "react";
+const count = signal(0);
+const state = new Signal.State(0);
+function Peek() {
+    return <p>{count.peek()}</p>;
+}
+function Untracked() {
+    return <p>{untracked(()=>count.value)}</p>;
+}
+function Method() {
+    var _effect = _useSignals();
+    try {
+        return <p>{state.get()}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+// `ReactiveRef` of `@preact-signals/utils`
+function Reactive({ reactiveRef }) {
+    var _effect = _useSignals();
+    try {
+        return <p>{reactiveRef.v}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+function Value() {
+    var _effect = _useSignals();
+    try {
+        return <p>{count.value}</p>;
+    } finally{
+        _effect.f();
+    }
+}
+function useMethod() {
+    var _effect = _useSignals();
+    try {
+        return state.get();
+    } finally{
+        _effect.f();
+    }
+}
+function usePeek() {
+    return count.peek();
+}
+// `.get()` in an effect isn't a render phase read
+function Effect() {
+    useEffect(()=>console.log(state.get()), []);
+    return <p/>;
+}
@@ -0,0 +1,26 @@
+import type { FC } from "react";
+import { memo } from "react";
+
+const As = (() => <div>{count.value}</div>) as FC;
+
+const Satisfies = ((props: Props) => <div>{props.count.value}</div>) satisfies FC<Props>;
+
+const NonNull = (() => <div />)!;
+
+const Const = (() => <div />) as const;
+
+const Memoized = memo((() => <div />) as any);
+
+const Generic = <T,>(props: Props<T>) => <div>{props.value}</div>;
+
+const Annotated: React.FC<Props> = () => <div />;
+
+export const Exported: FC = function () {
+  return <div />;
+} as FC;
+
+export default (() => <div />) satisfies FC;
+
+function useTyped<T>(value: T): T {
+  return count.value as T;
+}
@@ -0,0 +1,82 @@
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+import { memo } from "react";
+const As = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{count.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const Satisfies = (props)=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{props.count.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const NonNull = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+};
+const Const = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+};
+const Memoized = memo(()=>{
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+});
+const Generic = (props)=>{
+    var _effect = _useSignals();
+    try {
+        return <div>{props.value}</div>;
+    } finally{
+        _effect.f();
+    }
+};
+const Annotated = ()=>{
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+};
+export const Exported = function() {
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+};
+export default (()=>{
+    var _effect = _useSignals();
+    try {
+        return <div/>;
+    } finally{
+        _effect.f();
+    }
+});
+function useTyped(value) {
+    var _effect = _useSignals();
+    try {
+        return count.value;
+    } finally{
+        _effect.f();
+    }
+}
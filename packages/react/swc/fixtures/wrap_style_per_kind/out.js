@@ -0,0 +1,33 @@
+import { trackClassSignals as _trackClassSignals } from "@preact-signals/safe-react/tracking";
+import { useSignals as _useSignals } from "@preact-signals/safe-react/tracking";
+function A() {
+    "use memo";
+    using _effect = _useSignals(1);
+    return <div>{sig.value}</div>;
+}
+const B = ()=>{
+    using _effect = _useSignals(1);
+    return <div>{sig.value}</div>;
+};
+function useValue() {
+    var _effect = _useSignals(2);
+    try {
+        return sig.value;
+    } finally{
+        _effect.f();
+    }
+}
+class C extends Component {
+    render() {
+        using _effect = _trackClassSignals(this);
+        return <div>{sig.value}</div>;
+    }
+    componentDidMount() {
+        _trackClassSignals.m(this);
+        super.componentDidMount?.();
+    }
+    componentWillUnmount() {
+        _trackClassSignals.u(this);
+        super.componentWillUnmount?.();
+    }
+}
@@ -0,0 +1,8 @@
+function A() {
+  "use memo";
+  return <div>{sig.value}</div>;
+}
+const B = () => <div>{sig.value}</div>;
+function useValue() {
+  return sig.value;
+}
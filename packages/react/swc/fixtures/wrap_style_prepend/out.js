@@ -0,0 +1,14 @@
+import { useSignals as _useSignals } from "@preact/signals-react/runtime";
+function A() {
+    "use memo";
+    _useSignals();
+    return <div>{sig.value}</div>;
+}
+const B = ()=>{
+    _useSignals();
+    return <div>{sig.value}</div>;
+};
+function useValue() {
+    _useSignals();
+    return sig.value;
+}
@@ -0,0 +1,13 @@
+function A() {
+  "use memo";
+  return <div>{sig.value}</div>;
+}
+const B = () => <div>{sig.value}</div>;
+function useValue() {
+  return sig.value;
+}
+class C extends Component {
+  render() {
+    return <div>{sig.value}</div>;
+  }
+}
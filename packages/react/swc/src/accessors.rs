@@ -0,0 +1,77 @@
+use swc_core::ecma::ast::*;
+
+use crate::options::SignalAccessors;
+
+/// `value` for `.value` and `["value"]`
+fn prop_name(prop: &MemberProp) -> Option<&str> {
+    match prop {
+        MemberProp::Ident(ident) => Some(ident.sym.as_str()),
+        MemberProp::Computed(ComputedPropName { expr, .. })
+            if let Expr::Lit(Lit::Str(str)) = expr.unwrap_parens() =>
+        {
+            Some(str.value.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Property reads and method calls which read signals, and calls which read them without
+/// tracking.
+#[derive(Debug, Clone)]
+pub struct Accessors {
+    properties: Vec<String>,
+    methods: Vec<String>,
+    untracked: Vec<String>,
+}
+
+impl Default for Accessors {
+    fn default() -> Self {
+        Accessors::from_options(&SignalAccessors::default())
+    }
+}
+
+impl Accessors {
+    pub fn from_options(options: &SignalAccessors) -> Self {
+        Accessors {
+            properties: options.properties.clone(),
+            methods: options.methods.clone(),
+            untracked: options.untracked.clone(),
+        }
+    }
+
+    fn is_untracked(&self, name: &str) -> bool {
+        self.untracked.iter().any(|it| it == name)
+    }
+
+    /// object of `sig.value`
+    pub fn property_read<'a>(&self, n: &'a MemberExpr) -> Option<&'a Expr> {
+        let name = prop_name(&n.prop)?;
+        (!self.is_untracked(name) && self.properties.iter().any(|it| it == name)).then_some(&n.obj)
+    }
+
+    /// object of `sig.get()`
+    pub fn method_read<'a>(&self, n: &'a CallExpr) -> Option<&'a Expr> {
+        let Callee::Expr(callee) = &n.callee else {
+            return None;
+        };
+        let Expr::Member(member) = callee.unwrap_parens() else {
+            return None;
+        };
+        let name = prop_name(&member.prop)?;
+        (!self.is_untracked(name) && self.methods.iter().any(|it| it == name))
+            .then_some(&member.obj)
+    }
+
+    /// `sig.peek()` or `untracked(() => sig.value)`, nothing read inside counts
+    pub fn is_untracked_call(&self, n: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &n.callee else {
+            return false;
+        };
+        let name = match callee.unwrap_parens() {
+            Expr::Ident(ident) => Some(ident.sym.as_str()),
+            Expr::Member(member) => prop_name(&member.prop),
+            _ => None,
+        };
+        name.is_some_and(|it| self.is_untracked(it))
+    }
+}
@@ -0,0 +1,99 @@
+//! Native pre-pass writing the signal manifest read by `auto` mode.
+//!
+//! ```sh
+//! cargo build --release --features prepass --bin signal-manifest
+//! signal-manifest --root . --out signal-manifest.json src/**/*.{ts,tsx}
+//! ```
+//!
+//! `--check` fails instead of writing when the manifest is outdated. `--options` takes a JSON
+//! file with the plugin options, so `unknownOrigin` and `signalAccessors` match the plugin.
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use swc_plugin_preact_signals::{
+    accessors::Accessors, manifest::SignalManifest, options::PreactSignalsPluginOptions,
+};
+
+const USAGE: &str =
+    "usage: signal-manifest [--root <dir>] [--out <file>] [--check] [--options <file>] <files>...";
+
+struct Args {
+    root: Option<String>,
+    out: Option<PathBuf>,
+    check: bool,
+    options: PreactSignalsPluginOptions,
+    files: Vec<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        root: None,
+        out: None,
+        check: false,
+        options: PreactSignalsPluginOptions::default(),
+        files: vec![],
+    };
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().ok_or_else(|| format!("{name} expects a value"));
+        match arg.as_str() {
+            "--root" => args.root = Some(value("--root")?),
+            "--out" => args.out = Some(value("--out")?.into()),
+            "--check" => args.check = true,
+            "--options" => {
+                let path = value("--options")?;
+                let json = fs::read_to_string(&path)
+                    .map_err(|error| format!("can't read '{path}': {error}"))?;
+                args.options = PreactSignalsPluginOptions::from_json(&json)
+                    .map_err(|error| format!("invalid options in '{path}': {error}"))?;
+            }
+            arg if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            file => args.files.push(file.into()),
+        }
+    }
+    if args.check && args.out.is_none() {
+        return Err("--check expects --out".into());
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let accessors = Accessors::from_options(&args.options.signal_accessors);
+    let manifest = match SignalManifest::build(
+        &args.files,
+        args.root.as_deref(),
+        &accessors,
+        args.options.unknown_origin,
+    ) {
+        Ok(manifest) => manifest.to_json(),
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(out) = args.out else {
+        print!("{manifest}");
+        return ExitCode::SUCCESS;
+    };
+    if args.check {
+        if fs::read_to_string(&out).is_ok_and(|it| it == manifest) {
+            return ExitCode::SUCCESS;
+        }
+        eprintln!("'{}' is outdated", out.display());
+        return ExitCode::FAILURE;
+    }
+    match fs::write(&out, manifest) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("can't write '{}': {error}", out.display());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::ecma::{
+    ast::*,
+    utils::find_pat_ids,
+    visit::{Visit, VisitWith},
+};
+
+use crate::{
+    accessors::Accessors,
+    options::UnknownOrigin,
+    render_scope::{RenderScope, RenderScoped},
+    utils::ExprExt,
+};
+
+const SIGNALS_SOURCES: &[&str] = &[
+    "@preact/signals-core",
+    "@preact/signals",
+    "@preact/signals-react",
+    "@preact-signals/safe-react",
+    "@preact-signals/unified-signals",
+];
+const SIGNAL_FACTORIES: &[&str] = &["signal", "computed", "useSignal", "useComputed"];
+// values returned by these are known to never be signals, `ref.current.value` is the common case
+const NON_SIGNAL_FACTORIES: &[&str] = &["useRef", "createRef", "useState", "useReducer"];
+
+// guards against `const a = b, b = a`
+const MAX_ALIAS_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingOrigin {
+    Signal,
+    NotSignal,
+    Alias(Id),
+    Unknown,
+}
+
+fn is_non_signal_factory_call(call: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call.callee else {
+        return false;
+    };
+    match callee.unwrap_parens() {
+        Expr::Ident(ident) => NON_SIGNAL_FACTORIES.contains(&ident.sym.as_str()),
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => NON_SIGNAL_FACTORIES.contains(&prop.sym.as_str()),
+        _ => false,
+    }
+}
+
+/// Module-wide knowledge about where bindings come from.
+///
+/// Relies on bindings being resolved (unique `SyntaxContext` per scope), which swc guarantees for plugins.
+#[derive(Debug, Clone, Default)]
+pub struct SignalBindings {
+    origins: HashMap<Id, BindingOrigin>,
+    factories: HashSet<Id>,
+    namespaces: HashSet<Id>,
+}
+
+impl SignalBindings {
+    pub fn collect<N>(n: &N) -> SignalBindings
+    where
+        N: VisitWith<SignalBindings>,
+    {
+        let mut bindings = SignalBindings::default();
+        n.visit_with(&mut bindings);
+        bindings
+    }
+
+    pub fn origin(&self, id: &Id) -> &BindingOrigin {
+        self.origins.get(id).unwrap_or(&BindingOrigin::Unknown)
+    }
+
+    fn insert(&mut self, id: Id, origin: BindingOrigin) {
+        self.origins
+            .entry(id)
+            .and_modify(|it| {
+                if *it != origin {
+                    *it = BindingOrigin::Unknown
+                }
+            })
+            .or_insert(origin);
+    }
+
+    fn is_signal_factory_call(&self, call: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        match callee.unwrap_parens() {
+            Expr::Ident(ident) => self.factories.contains(&ident.to_id()),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) if let Expr::Ident(obj) = obj.unwrap_parens() => {
+                self.namespaces.contains(&obj.to_id())
+                    && SIGNAL_FACTORIES.contains(&prop.sym.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    fn origin_of_init(&self, init: Option<&Expr>) -> BindingOrigin {
+        let Some(init) = init else {
+            return BindingOrigin::Unknown;
+        };
+        match init.unwrap_ts() {
+            Expr::Call(call) if self.is_signal_factory_call(call) => BindingOrigin::Signal,
+            Expr::Call(call) if is_non_signal_factory_call(call) => BindingOrigin::NotSignal,
+            Expr::Ident(ident) => BindingOrigin::Alias(ident.to_id()),
+            Expr::Lit(_)
+            | Expr::Tpl(_)
+            | Expr::Fn(_)
+            | Expr::Arrow(_)
+            | Expr::Class(_)
+            | Expr::Bin(_)
+            | Expr::Unary(_)
+            | Expr::Update(_)
+            | Expr::JSXElement(_)
+            | Expr::JSXFragment(_) => BindingOrigin::NotSignal,
+            _ => BindingOrigin::Unknown,
+        }
+    }
+}
+
+impl Visit for SignalBindings {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only || !SIGNALS_SOURCES.contains(&n.src.value.as_str()) {
+            return;
+        }
+        for specifier in &n.specifiers {
+            match specifier {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    local,
+                    imported,
+                    is_type_only: false,
+                    ..
+                }) => {
+                    let imported = match imported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym.as_str(),
+                        Some(ModuleExportName::Str(str)) => str.value.as_str(),
+                        None => local.sym.as_str(),
+                    };
+                    if SIGNAL_FACTORIES.contains(&imported) {
+                        self.factories.insert(local.to_id());
+                    }
+                }
+                ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                    self.namespaces.insert(local.to_id());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        match &n.name {
+            Pat::Ident(ident) => {
+                let origin = self.origin_of_init(n.init.as_deref());
+                self.insert(ident.to_id(), origin);
+            }
+            pat => {
+                let origin = match n.init.as_deref().map(|it| it.unwrap_ts()) {
+                    Some(Expr::Call(call)) if is_non_signal_factory_call(call) => {
+                        BindingOrigin::NotSignal
+                    }
+                    _ => BindingOrigin::Unknown,
+                };
+                for id in find_pat_ids::<_, Id>(pat) {
+                    self.insert(id, origin.clone());
+                }
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Looks for render phase signal reads (`.value` by default) on bindings that may hold a signal.
+///
+/// Parameters of the analyzed function and of its render phase callbacks (`item` in
+/// `items.map(item => item.value)`) are treated as signals, as props may hold them.
+pub struct HasSignalRead<'a> {
+    bindings: &'a SignalBindings,
+    accessors: &'a Accessors,
+    unknown_origin: UnknownOrigin,
+    params: HashSet<Id>,
+    scope: RenderScope,
+    pub found: bool,
+}
+
+impl<'a> HasSignalRead<'a> {
+    pub fn new(
+        bindings: &'a SignalBindings,
+        accessors: &'a Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> Self {
+        HasSignalRead {
+            bindings,
+            accessors,
+            unknown_origin,
+            params: HashSet::new(),
+            scope: RenderScope::default(),
+            found: false,
+        }
+    }
+
+    /// params of functions which aren't part of the render are never read, as they aren't visited
+    fn add_params<'p>(&mut self, params: impl Iterator<Item = &'p Pat>) {
+        self.params.extend(params.flat_map(find_pat_ids::<_, Id>));
+    }
+
+    fn is_signal_id(&self, id: &Id, depth: usize) -> bool {
+        if self.params.contains(id) {
+            return true;
+        }
+        match self.bindings.origin(id) {
+            BindingOrigin::Signal => true,
+            BindingOrigin::NotSignal => false,
+            BindingOrigin::Alias(other) if depth < MAX_ALIAS_DEPTH => {
+                self.is_signal_id(other, depth + 1)
+            }
+            BindingOrigin::Alias(_) | BindingOrigin::Unknown => {
+                self.unknown_origin == UnknownOrigin::Signal
+            }
+        }
+    }
+
+    fn may_be_signal(&self, expr: &Expr) -> bool {
+        match expr.unwrap_ts() {
+            Expr::Ident(ident) => self.is_signal_id(&ident.to_id(), 0),
+            Expr::Member(member) => self.may_be_signal(&member.obj),
+            _ => self.unknown_origin == UnknownOrigin::Signal,
+        }
+    }
+}
+
+impl RenderScoped for HasSignalRead<'_> {
+    fn render_scope(&mut self) -> &mut RenderScope {
+        &mut self.scope
+    }
+}
+impl Visit for HasSignalRead<'_> {
+    fn visit_function(&mut self, n: &Function) {
+        self.add_params(n.params.iter().map(|it| &it.pat));
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.add_params(n.params.iter());
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.found || self.accessors.is_untracked_call(n) {
+            return;
+        }
+
+        if let Some(obj) = self.accessors.method_read(n)
+            && self.may_be_signal(obj)
+        {
+            self.found = true;
+            return;
+        }
+        self.visit_call_in_render_scope(n);
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if self.found {
+            return;
+        }
+
+        if let Some(obj) = self.accessors.property_read(n)
+            && self.may_be_signal(obj)
+        {
+            self.found = true;
+            return;
+        }
+        n.visit_children_with(self);
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::ecma::{
+    ast::*,
+    visit::{Visit, VisitWith},
+};
+
+use crate::{
+    accessors::Accessors,
+    bindings::{HasSignalRead, SignalBindings},
+    jsx::JsxFactories,
+    options::UnknownOrigin,
+    render_scope::{HasRenderJSX, RenderScope, RenderScoped},
+    utils::ExprExt,
+};
+
+/// Collects local functions called during render, `helper()` in `items.map(() => helper())`
+/// counts and in `useEffect(() => helper())` doesn't.
+#[derive(Default)]
+pub struct RenderCalls {
+    scope: RenderScope,
+    pub calls: HashSet<Id>,
+}
+impl RenderScoped for RenderCalls {
+    fn render_scope(&mut self) -> &mut RenderScope {
+        &mut self.scope
+    }
+}
+impl Visit for RenderCalls {
+    fn visit_function(&mut self, n: &Function) {
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Expr(callee) = &n.callee
+            && let Expr::Ident(ident) = callee.unwrap_parens()
+        {
+            self.calls.insert(ident.to_id());
+        }
+        self.visit_call_in_render_scope(n);
+    }
+}
+
+#[derive(Debug, Default)]
+struct LocalFunction {
+    reads_signal: bool,
+    renders_jsx: bool,
+    calls: HashSet<Id>,
+}
+
+/// `seeds` and local functions calling them, directly or through other local functions
+fn callers_of(functions: &HashMap<Id, LocalFunction>, seeds: HashSet<Id>) -> HashSet<Id> {
+    let mut found = seeds;
+    // grows until a fixed point, so cycles (`a` calls `b` calls `a`) terminate
+    loop {
+        let callers = functions
+            .iter()
+            .filter(|(id, it)| {
+                !found.contains(*id) && it.calls.iter().any(|call| found.contains(call))
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        if callers.is_empty() {
+            return found;
+        }
+        found.extend(callers);
+    }
+}
+
+/// Local functions and hooks of a module which read signals during render, directly or
+/// through other local functions.
+///
+/// Collected once per module, so `auto` mode can track `function Card() { const n = useCount(); .. }`
+/// when `useCount` reads `count.value`, even if `useCount` isn't tracked itself. Imported
+/// functions listed in the signal manifest are readers as well. Local functions rendering
+/// JSX are collected the same way, so `return renderRow()` renders JSX.
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    readers: HashSet<Id>,
+    renderers: HashSet<Id>,
+}
+
+impl CallGraph {
+    pub fn collect<N>(
+        n: &N,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+        factories: &JsxFactories,
+        imported: HashSet<Id>,
+    ) -> Self
+    where
+        N: for<'a> VisitWith<LocalFunctions<'a>>,
+    {
+        let mut collector = LocalFunctions {
+            bindings,
+            accessors,
+            unknown_origin,
+            factories,
+            functions: HashMap::new(),
+        };
+        n.visit_with(&mut collector);
+        let functions = collector.functions;
+
+        let mut readers = imported;
+        readers.extend(
+            functions
+                .iter()
+                .filter(|(_, it)| it.reads_signal)
+                .map(|(id, _)| id.clone()),
+        );
+        let renderers = functions
+            .iter()
+            .filter(|(_, it)| it.renders_jsx)
+            .map(|(id, _)| id.clone())
+            .collect();
+        CallGraph {
+            readers: callers_of(&functions, readers),
+            renderers: callers_of(&functions, renderers),
+        }
+    }
+
+    /// any of `calls` reads signals
+    pub fn calls_signal_reader(&self, calls: &HashSet<Id>) -> bool {
+        calls.iter().any(|it| self.readers.contains(it))
+    }
+
+    /// any of `calls` renders JSX
+    pub fn calls_renderer(&self, calls: &HashSet<Id>) -> bool {
+        calls.iter().any(|it| self.renderers.contains(it))
+    }
+
+    pub fn is_reader(&self, id: &Id) -> bool {
+        self.readers.contains(id)
+    }
+
+    /// `Detectable::has_signal_read` with calls of readers, for functions without a binding
+    pub fn reads_signals<N>(
+        &self,
+        n: &N,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool
+    where
+        N: for<'a> VisitWith<HasSignalRead<'a>> + VisitWith<RenderCalls>,
+    {
+        let mut signal_read = HasSignalRead::new(bindings, accessors, unknown_origin);
+        n.visit_with(&mut signal_read);
+        let mut render_calls = RenderCalls::default();
+        n.visit_with(&mut render_calls);
+        signal_read.found || self.calls_signal_reader(&render_calls.calls)
+    }
+}
+
+pub struct LocalFunctions<'a> {
+    bindings: &'a SignalBindings,
+    accessors: &'a Accessors,
+    unknown_origin: UnknownOrigin,
+    factories: &'a JsxFactories,
+    functions: HashMap<Id, LocalFunction>,
+}
+
+impl LocalFunctions<'_> {
+    fn add<N>(&mut self, id: Id, n: &N)
+    where
+        N: for<'a> VisitWith<HasSignalRead<'a>>
+            + for<'a> VisitWith<HasRenderJSX<'a>>
+            + VisitWith<RenderCalls>,
+    {
+        let mut signal_read =
+            HasSignalRead::new(self.bindings, self.accessors, self.unknown_origin);
+        n.visit_with(&mut signal_read);
+        let mut render_jsx = HasRenderJSX::new(self.factories);
+        n.visit_with(&mut render_jsx);
+        let mut render_calls = RenderCalls::default();
+        n.visit_with(&mut render_calls);
+        self.functions.insert(
+            id,
+            LocalFunction {
+                reads_signal: signal_read.found,
+                renders_jsx: render_jsx.found,
+                calls: render_calls.calls,
+            },
+        );
+    }
+}
+
+impl Visit for LocalFunctions<'_> {
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.add(n.ident.to_id(), &*n.function);
+        n.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let Pat::Ident(ident) = &n.name
+            && let Some(init) = &n.init
+        {
+            match init.unwrap_ts() {
+                Expr::Arrow(arrow) => self.add(ident.to_id(), arrow),
+                Expr::Fn(FnExpr { function, .. }) => self.add(ident.to_id(), &**function),
+                _ => {}
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
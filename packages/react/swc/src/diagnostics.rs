@@ -0,0 +1,155 @@
+use swc_core::common::{errors::HANDLER, Span};
+
+use crate::{manifest::ManifestError, options::OptionsError};
+
+pub fn conflicting_directives(opt_in: Span, opt_out: Span) {
+    HANDLER.with(|handler| {
+        let mut diagnostic = handler.struct_span_warn(
+            opt_out,
+            "function is marked with both `@useSignals` and `@noUseSignals`, it won't be tracked",
+        );
+        // both directives may be in the same comment
+        if opt_in != opt_out {
+            diagnostic.span_label(opt_in, "opted in here");
+        }
+        diagnostic.emit()
+    });
+}
+
+pub fn directive_on_non_function(directive: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                directive,
+                "signals directive has no effect here, it should be attached to a function or to a variable, property or export holding a function",
+            )
+            .emit()
+    });
+}
+
+pub fn opt_in_not_regular_function(opt_in: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                opt_in,
+                "async and generator functions can't be tracked, `@useSignals` is ignored",
+            )
+            .emit()
+    });
+}
+
+pub fn opt_in_worklet(opt_in: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                opt_in,
+                "worklets run outside of React and can't be tracked, `@useSignals` is ignored",
+            )
+            .emit()
+    });
+}
+
+pub fn opt_in_kind_off(opt_in: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                opt_in,
+                "tracking of this kind of functions is turned off by `mode`, `@useSignals` is ignored",
+            )
+            .emit()
+    });
+}
+
+pub fn opt_in_server_module(opt_in: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                opt_in,
+                "server components can't call hooks, `@useSignals` is ignored. Add `\"use client\"` to the module to track it",
+            )
+            .emit()
+    });
+}
+
+pub fn opt_in_server_action(opt_in: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                opt_in,
+                "server actions run on the server and can't be tracked, `@useSignals` is ignored",
+            )
+            .emit()
+    });
+}
+
+pub fn class_not_tracked_by_runtime(class: Span) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                class,
+                "runtime has no `trackClassSignals`, so class components can't be tracked and this one is left as is",
+            )
+            .emit()
+    });
+}
+
+pub fn class_lifecycle_not_wired(class: Span, lifecycle: &str) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_warn(
+                class,
+                &format!(
+                    "`{lifecycle}` of this class component isn't a method or a function, so signal subscriptions can't be added to it. Call `trackClassSignals.m(this)` in `componentDidMount` and `trackClassSignals.u(this)` in `componentWillUnmount` by hand"
+                ),
+            )
+            .emit()
+    });
+}
+
+pub fn manual_use_signals_skipped(call: Span) {
+    HANDLER.with(|handler| {
+        handler.span_note_without_error(
+            call,
+            "function calls `useSignals` by hand, it's left as is. Set `manualUseSignals` to `\"rewrite\"` to let the plugin instrument it",
+        )
+    });
+}
+
+pub fn manual_use_signals_rewritten(call: Span) {
+    HANDLER.with(|handler| {
+        handler.span_note_without_error(
+            call,
+            "hand-written `useSignals` call is replaced by the plugin's instrumentation",
+        )
+    });
+}
+
+pub fn prepend_not_finished() {
+    HANDLER.with(|handler| {
+        handler
+            .struct_warn(
+                "`wrapStyle` \"prepend\" needs a runtime finishing stores by itself, `try-finally` is used instead",
+            )
+            .emit()
+    });
+}
+
+pub fn invalid_config(error: &OptionsError) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_err(&format!(
+                "invalid @preact-signals/safe-react/swc plugin config: {error}"
+            ))
+            .emit()
+    });
+}
+
+pub fn manifest_unreadable(error: &ManifestError) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_warn(&format!(
+                "{error}, imported functions are treated as not reading signals"
+            ))
+            .emit()
+    });
+}
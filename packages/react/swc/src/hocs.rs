@@ -0,0 +1,110 @@
+use swc_core::ecma::ast::*;
+
+use crate::{
+    options::{HocConfig, HocOption, HocsOption, HocsPreset},
+    utils::ExprExt,
+};
+
+const REACT_HOCS: &[&str] = &["memo", "forwardRef", "React.memo", "React.forwardRef"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HocMatcher {
+    callee: Vec<String>,
+    argument: usize,
+}
+
+impl HocMatcher {
+    fn new(callee: &str, argument: usize) -> Self {
+        HocMatcher {
+            callee: callee.split('.').map(str::to_owned).collect(),
+            argument,
+        }
+    }
+}
+
+/// Calls whose arguments are searched for components.
+#[derive(Debug, Clone, Default)]
+pub struct Hocs {
+    /// `None` means any call with the component as the first argument
+    matchers: Option<Vec<HocMatcher>>,
+}
+
+impl Hocs {
+    pub fn from_option(option: &HocsOption) -> Self {
+        let matchers = match option {
+            HocsOption::Preset(HocsPreset::Any) => None,
+            HocsOption::Preset(HocsPreset::React) => Some(
+                REACT_HOCS
+                    .iter()
+                    .map(|callee| HocMatcher::new(callee, 0))
+                    .collect(),
+            ),
+            HocsOption::List(list) => Some(
+                list.iter()
+                    .map(|it| match it {
+                        HocOption::Callee(callee) => HocMatcher::new(callee, 0),
+                        HocOption::Config(HocConfig { callee, argument }) => {
+                            HocMatcher::new(callee, *argument)
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        Hocs { matchers }
+    }
+
+    fn argument_index(&self, call: &CallExpr) -> Option<usize> {
+        let Some(matchers) = &self.matchers else {
+            return Some(0);
+        };
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let path = callee_path(root_callee(callee))?;
+        matchers
+            .iter()
+            .find(|it| it.callee.iter().eq(path.iter()))
+            .map(|it| it.argument)
+    }
+
+    /// Argument of `call` that may hold a component
+    pub fn component_arg<'a>(&self, call: &'a mut CallExpr) -> Option<&'a mut Expr> {
+        let index = self.argument_index(call)?;
+        // arguments after a spread can't be located
+        if call.args.iter().take(index).any(|it| it.spread.is_some()) {
+            return None;
+        }
+        match call.args.get_mut(index) {
+            Some(ExprOrSpread { spread: None, expr }) => Some(expr),
+            _ => None,
+        }
+    }
+}
+
+/// `connect` for `connect(mapState)(Comp)`
+fn root_callee(mut expr: &Expr) -> &Expr {
+    while let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = expr.unwrap_ts()
+    {
+        expr = callee;
+    }
+    expr.unwrap_ts()
+}
+
+pub fn callee_path(expr: &Expr) -> Option<Vec<&str>> {
+    match expr.unwrap_ts() {
+        Expr::Ident(ident) => Some(vec![ident.sym.as_str()]),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            let mut path = callee_path(obj)?;
+            path.push(prop.sym.as_str());
+            Some(path)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use swc_core::ecma::{
+    ast::*,
+    visit::{Visit, VisitWith},
+};
+
+use crate::{hocs::callee_path, utils::ExprExt};
+
+/// modules exporting element factories and names of the factories
+const JSX_SOURCES: &[(&str, &[&str])] = &[
+    ("react", &["createElement"]),
+    ("react/jsx-runtime", &["jsx", "jsxs"]),
+    ("react/jsx-dev-runtime", &["jsxDEV"]),
+];
+// classic runtime output works with a global `React` as well
+const DEFAULT_PRAGMA: &str = "React.createElement";
+
+fn factory_names(source: &str) -> Option<&'static [&'static str]> {
+    JSX_SOURCES
+        .iter()
+        .find(|(it, _)| *it == source)
+        .map(|(_, names)| *names)
+}
+
+fn is_factory_name(name: &str) -> bool {
+    JSX_SOURCES.iter().any(|(_, names)| names.contains(&name))
+}
+
+/// `"react"` for `require("react")` and `_interop_require_default(require("react"))`
+fn required_source(expr: &Expr) -> Option<&str> {
+    let Expr::Call(call) = expr.unwrap_ts() else {
+        return None;
+    };
+    let arg = call.args.first()?;
+    match &call.callee {
+        Callee::Expr(callee)
+            if let Expr::Ident(ident) = callee.unwrap_parens()
+                && ident.sym == "require" =>
+        {
+            match arg.expr.unwrap_parens() {
+                Expr::Lit(Lit::Str(str)) => Some(str.value.as_str()),
+                _ => None,
+            }
+        }
+        // interop helpers added by module transforms
+        _ => required_source(&arg.expr),
+    }
+}
+
+/// `_jsx` for `(0, _jsx)`, as emitted by module transforms
+fn unwrap_indirect_call(expr: &Expr) -> &Expr {
+    match expr.unwrap_parens() {
+        Expr::Seq(SeqExpr { exprs, .. }) if let Some(last) = exprs.last() => last.unwrap_parens(),
+        expr => expr,
+    }
+}
+
+/// Calls which create elements, so code already compiled by a JSX transform counts as JSX.
+///
+/// Like `SignalBindings`, relies on bindings being resolved.
+#[derive(Debug, Clone, Default)]
+pub struct JsxFactories {
+    /// `["h"]`, `["Preact", "h"]`
+    pragmas: Vec<Vec<String>>,
+    /// locals bound to a factory: `createElement`, `_jsx`
+    factories: HashSet<Id>,
+    /// locals bound to a module exporting factories: `React`, `_jsxRuntime`
+    namespaces: HashSet<Id>,
+}
+
+impl JsxFactories {
+    pub fn new(pragmas: &[String]) -> Self {
+        JsxFactories {
+            pragmas: pragmas
+                .iter()
+                .map(String::as_str)
+                .chain([DEFAULT_PRAGMA])
+                .map(|it| it.split('.').map(str::to_owned).collect())
+                .collect(),
+            factories: HashSet::new(),
+            namespaces: HashSet::new(),
+        }
+    }
+
+    /// collects factories imported or required by the file, replacing those of the previous one
+    pub fn collect<N>(&mut self, n: &N)
+    where
+        N: VisitWith<JsxFactories>,
+    {
+        self.factories.clear();
+        self.namespaces.clear();
+        n.visit_with(self);
+    }
+
+    fn is_namespace(&self, expr: &Expr) -> bool {
+        match expr.unwrap_ts() {
+            Expr::Ident(ident) => self.namespaces.contains(&ident.to_id()),
+            // `_react.default.createElement` for default imports compiled to CommonJS
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) if prop.sym == "default" => self.is_namespace(obj),
+            _ => false,
+        }
+    }
+
+    /// `createElement(..)`, `_jsx(..)`, `(0, _jsxRuntime.jsx)(..)` or a pragma call
+    pub fn is_factory_call(&self, call: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+        let callee = unwrap_indirect_call(callee);
+        let is_bound = match callee {
+            Expr::Ident(ident) => self.factories.contains(&ident.to_id()),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => is_factory_name(&prop.sym) && self.is_namespace(obj),
+            _ => false,
+        };
+        is_bound
+            || callee_path(callee).is_some_and(|path| {
+                self.pragmas
+                    .iter()
+                    .any(|pragma| pragma.iter().eq(path.iter()))
+            })
+    }
+}
+
+impl Visit for JsxFactories {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only {
+            return;
+        }
+        let Some(names) = factory_names(&n.src.value) else {
+            return;
+        };
+        for specifier in &n.specifiers {
+            match specifier {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    local,
+                    imported,
+                    is_type_only: false,
+                    ..
+                }) => {
+                    let imported = match imported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym.as_str(),
+                        Some(ModuleExportName::Str(str)) => str.value.as_str(),
+                        None => local.sym.as_str(),
+                    };
+                    if names.contains(&imported) {
+                        self.factories.insert(local.to_id());
+                    }
+                }
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                    self.namespaces.insert(local.to_id());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let Some(init) = &n.init
+            && let Some(names) = required_source(init).and_then(factory_names)
+        {
+            match &n.name {
+                Pat::Ident(ident) => {
+                    self.namespaces.insert(ident.to_id());
+                }
+                // `const { jsx: _jsx } = require("react/jsx-runtime")`
+                Pat::Object(ObjectPat { props, .. }) => {
+                    for prop in props {
+                        match prop {
+                            ObjectPatProp::KeyValue(KeyValuePatProp {
+                                key: PropName::Ident(key),
+                                value: box Pat::Ident(local),
+                            }) if names.contains(&key.sym.as_str()) => {
+                                self.factories.insert(local.to_id());
+                            }
+                            ObjectPatProp::Assign(AssignPatProp { key, .. })
+                                if names.contains(&key.sym.as_str()) =>
+                            {
+                                self.factories.insert(key.to_id());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
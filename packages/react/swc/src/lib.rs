@@ -1,25 +1,49 @@
-#![feature(box_patterns, let_chains, if_let_guard, slice_take)]
+#![feature(box_patterns, let_chains, if_let_guard)]
 
+pub mod accessors;
+pub mod bindings;
+pub mod call_graph;
+pub mod diagnostics;
+pub mod hocs;
+pub mod jsx;
+pub mod manifest;
+pub mod manual_tracking;
+pub mod naming;
+#[cfg(feature = "prepass")]
+pub mod prepass;
+pub mod render_scope;
+pub mod rsc;
+pub mod runtime;
 pub mod utils;
-use swc_core::common::SyntaxContext;
+use accessors::Accessors;
+use bindings::SignalBindings;
+use call_graph::CallGraph;
+use hocs::Hocs;
+use jsx::JsxFactories;
+use manifest::{ImportedReaders, SignalManifest};
+use manual_tracking::{find_manual_call, remove_manual_tracking};
+use naming::{FileName, Naming};
+use rsc::{Rsc, USE_SERVER_DIRECTIVE};
+use runtime::Runtime;
 use utils::*;
 
 use std::{
     borrow::BorrowMut,
+    collections::HashSet,
     fmt::Debug,
+    mem,
     ops::{Deref, DerefMut},
+    path::Path,
 };
 
 use regex::Regex;
-use std::path::PathBuf;
 use swc_core::{
     common::comments::Comments,
-    common::{comments::CommentKind, sync::Lazy, Span, DUMMY_SP},
+    common::{sync::Lazy, BytePos, Span, Spanned as _},
     ecma::{
         ast::*,
-        atoms::Atom,
-        utils::{prepend_stmt, private_ident},
-        visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
+        utils::prepend_stmt,
+        visit::{noop_visit_mut_type, VisitMut, VisitMutWith, VisitWith},
     },
     plugin::{
         metadata::TransformPluginMetadataContextKind, plugin_transform,
@@ -29,100 +53,630 @@ use swc_core::{
 
 fn is_track_signals_directive(string: &str) -> bool {
     // https://github.com/preactjs/signals/blob/e04671469e9272de356109170b2e429db49db2f0/packages/react-transform/src/index.ts#L18
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\s|^)@useSignals(\s|$)"#).unwrap());
+    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\s|^|\*)@useSignals(\s|$)"#).unwrap());
 
     RE.is_match(string)
 }
 fn is_no_track_signals_directive(string: &str) -> bool {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\s|^)@noUseSignals(\s|$)"#).unwrap());
+    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\s|^|\*)@noUseSignals(\s|$)"#).unwrap());
 
     RE.is_match(string)
 }
 
-trait StrExt {
-    fn from_str(str: &str) -> Str;
-    fn signals_default_source() -> Str;
-}
-impl StrExt for Str {
-    fn from_str(str: &str) -> Str {
-        Str {
-            span: DUMMY_SP,
-            value: Atom::new(str),
-            raw: None,
+pub mod options {
+    use std::fmt::Display;
+
+    use regex::Regex;
+    use schemars::JsonSchema;
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+    use serde_json::Value;
+    use swc_core::common::sync::Lazy;
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum TransformMode {
+        /// only functions marked with `@useSignals` are tracked
+        Manual,
+        /// all options affects only components
+        #[default]
+        All,
+        /// components and hooks which read signals during render are tracked
+        Auto,
+    }
+
+    /// policy for a single kind of functions
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum KindMode {
+        /// functions which read signals during render are tracked
+        Auto,
+        /// components using JSX and hooks using `.value` are tracked
+        #[default]
+        All,
+        /// only functions marked with `@useSignals` are tracked
+        Manual,
+        /// functions aren't tracked, even with `@useSignals`
+        Off,
+    }
+
+    impl From<TransformMode> for KindMode {
+        fn from(mode: TransformMode) -> Self {
+            match mode {
+                TransformMode::Manual => KindMode::Manual,
+                TransformMode::All => KindMode::All,
+                TransformMode::Auto => KindMode::Auto,
+            }
         }
     }
-    fn signals_default_source() -> Str {
-        Str::from_str("@preact-signals/safe-react/tracking")
+
+    /// kind of `@useSignals` functions which names are neither component nor hook names
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum UnknownKind {
+        /// tracked without usage flag
+        #[default]
+        Unknown,
+        Component,
+        Hook,
     }
-}
-trait IdentExt {
-    fn use_signals(ctxt: SyntaxContext) -> Ident;
-}
-impl IdentExt for Ident {
-    fn use_signals(ctxt: SyntaxContext) -> Ident {
-        Ident {
-            span: DUMMY_SP,
-            sym: "useSignals".into(),
-            ctxt,
-            optional: false,
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct KindModes {
+        #[serde(default)]
+        pub components: KindMode,
+        #[serde(default)]
+        pub hooks: KindMode,
+        #[serde(default)]
+        pub unknown_kind: UnknownKind,
+    }
+
+    /// which functions are tracked without `@useSignals`, for all kinds or separately for
+    /// components and hooks
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone, Copy)]
+    #[serde(untagged)]
+    pub enum ModeOption {
+        Mode(TransformMode),
+        PerKind(KindModes),
+    }
+    impl Default for ModeOption {
+        fn default() -> Self {
+            ModeOption::Mode(TransformMode::default())
         }
     }
-}
 
-pub mod options {
-    use serde::Deserialize;
+    impl ModeOption {
+        /// `transform_hooks: false` keeps hooks from being tracked without `@useSignals`
+        pub fn modes(&self, transform_hooks: bool) -> KindModes {
+            let mut modes = match *self {
+                ModeOption::Mode(mode) => KindModes {
+                    components: mode.into(),
+                    hooks: mode.into(),
+                    unknown_kind: UnknownKind::default(),
+                },
+                ModeOption::PerKind(modes) => modes,
+            };
+            if !transform_hooks && matches!(modes.hooks, KindMode::Auto | KindMode::All) {
+                modes.hooks = KindMode::Manual;
+            }
+            modes
+        }
+    }
 
-    #[derive(PartialEq, Eq, Deserialize, Default, Debug, Clone, Copy)]
+    /// how `auto` mode treats `.value` reads on bindings it can't trace back to a signal
+    /// (imports from other modules, globals, results of custom hooks)
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
     #[serde(rename_all = "kebab-case")]
-    pub enum TransformMode {
-        Manual,
-        /**
-         * all options affects only components
-         */
+    pub enum UnknownOrigin {
         #[default]
-        All,
-        Auto,
+        Signal,
+        Ignore,
     }
 
-    #[derive(Deserialize, Debug, Clone, Copy)]
-    #[serde(rename_all = "camelCase")]
-    pub struct PreactSignalsPluginExperimental {
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum HocsPreset {
+        /// first argument of any call is treated as a possible component
+        #[default]
+        Any,
+        /// `memo` and `forwardRef`, with or without `React.` prefix
+        React,
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged, deny_unknown_fields)]
+    pub enum HocOption {
+        /// `"memo"` or `"React.memo"`, component is the first argument
+        Callee(String),
+        Config(HocConfig),
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct HocConfig {
+        /// callee name or member path
+        pub callee: String,
+        /// index of the argument holding the component
         #[serde(default)]
-        pub add_hook_usage_flag: bool,
+        pub argument: usize,
+    }
+
+    /// calls that may wrap a component, matched by callee name or member path.
+    /// For curried calls (`connect(mapState)(Comp)`) the innermost callee is matched and
+    /// the argument index applies to the last call
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum HocsOption {
+        Preset(HocsPreset),
+        List(Vec<HocOption>),
+    }
+    impl Default for HocsOption {
+        fn default() -> Self {
+            HocsOption::Preset(HocsPreset::default())
+        }
+    }
+
+    /// what to do with functions calling `useSignals` by hand
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum ManualUseSignals {
+        /// leave them as is
+        #[default]
+        Skip,
+        /// remove hand-written calls and wrap the function the same way as others, functions
+        /// which calls can't be removed are skipped
+        Rewrite,
+    }
+
+    /// shape of the code tracking a function
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum WrapStyle {
+        /// `var _effect = _useSignals(); try { ... } finally { _effect.f(); }`
+        #[default]
+        TryFinally,
+        /// `using _effect = _useSignals();`, requires explicit resource management support
+        Using,
+        /// `_useSignals();`, only for runtimes finishing stores by themselves, others use
+        /// `try-finally` instead
+        Prepend,
+    }
+
+    /// `WrapStyle` for all functions or separately for components and hooks. Class components
+    /// use the component style, with try/finally in place of `prepend`
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone, Copy)]
+    #[serde(untagged)]
+    pub enum WrapStyleOption {
+        Style(WrapStyle),
+        PerKind(WrapStyles),
+    }
+    impl Default for WrapStyleOption {
+        fn default() -> Self {
+            WrapStyleOption::Style(WrapStyle::default())
+        }
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct WrapStyles {
+        #[serde(default)]
+        pub components: WrapStyle,
+        #[serde(default)]
+        pub hooks: WrapStyle,
+    }
+
+    impl WrapStyleOption {
+        pub fn styles(&self) -> WrapStyles {
+            match *self {
+                WrapStyleOption::Style(style) => WrapStyles {
+                    components: style,
+                    hooks: style,
+                },
+                WrapStyleOption::PerKind(styles) => styles,
+            }
+        }
+    }
+
+    impl WrapStyles {
+        pub fn has_prepend(&self) -> bool {
+            self.components == WrapStyle::Prepend || self.hooks == WrapStyle::Prepend
+        }
+
+        /// `prepend` replaced with `try-finally`
+        pub fn without_prepend(self) -> Self {
+            let replace = |style| match style {
+                WrapStyle::Prepend => WrapStyle::TryFinally,
+                style => style,
+            };
+            WrapStyles {
+                components: replace(self.components),
+                hooks: replace(self.hooks),
+            }
+        }
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum RuntimePreset {
+        /// `@preact-signals/safe-react/tracking`
+        #[default]
+        SafeReact,
+        /// `@preact/signals-react/runtime`, it has no `trackClassSignals`, so class components
+        /// aren't tracked
+        PreactSignalsReact,
+    }
+
+    /// how tracking functions are imported
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum ImportKind {
+        /// `import { useSignals as _useSignals } from "source"`
+        #[default]
+        Named,
+        /// `import _useSignals from "source"`, `trackClassSignals` is still a named import
+        Default,
+        /// `import * as _signalsRuntime from "source"`, functions are called as its members
+        Namespace,
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum UsageArg {
+        Number(i64),
+        String(String),
+    }
+
+    /// arguments passed to `useSignals` with `experimental.addHookUsageFlag`
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(deny_unknown_fields)]
+    pub struct UsageArgs {
+        pub component: UsageArg,
+        pub hook: UsageArg,
+    }
+
+    fn default_use_signals() -> String {
+        "useSignals".into()
+    }
+    fn default_finish() -> String {
+        "f".into()
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct CustomRuntime {
+        /// module tracking functions are imported from
+        pub source: String,
+        #[serde(default)]
+        pub import: ImportKind,
+        /// export (or namespace member) creating the effect store in function components and hooks
+        #[serde(default = "default_use_signals")]
+        pub use_signals: String,
+        /// export (or namespace member) called with `this` in `render` of class components,
+        /// class components aren't tracked without it. Its `m` and `u` members are called with
+        /// `this` in `componentDidMount` and `componentWillUnmount`
+        #[serde(default)]
+        pub track_class_signals: Option<String>,
+        /// method of the effect store called in `finally`
+        #[serde(default = "default_finish")]
+        pub finish: String,
+        /// usage arguments, `useSignals` is called without arguments when they aren't set
+        #[serde(default)]
+        pub usage: Option<UsageArgs>,
+        /// stores created by `useSignals` are finished by the runtime itself, which `prepend`
+        /// wrap style relies on
+        #[serde(default)]
+        pub finishes_stores: bool,
+    }
+
+    /// runtime providing tracking functions, a preset or a custom one
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum RuntimeOption {
+        Preset(RuntimePreset),
+        Custom(CustomRuntime),
+    }
+    impl Default for RuntimeOption {
+        fn default() -> Self {
+            RuntimeOption::Preset(RuntimePreset::default())
+        }
+    }
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, Debug, Clone, Copy)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum FileNamingPreset {
+        /// file name is used as is, `Button.tsx` is a component
+        #[default]
+        Raw,
+        /// query and extensions are stripped, kebab and snake case names are converted to
+        /// PascalCase (`use-` prefixed ones to camelCase), `index` files are named after their
+        /// directory
+        Normalized,
+        /// `normalized`, Next.js route files (`page`, `layout`, etc.) are components
+        Next,
     }
-    impl Default for PreactSignalsPluginExperimental {
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct CustomFileNaming {
+        /// `normalized` file names, which are components
+        pub route_files: Vec<String>,
+    }
+
+    /// how anonymous default exports are named after their file
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum FileNamingOption {
+        Preset(FileNamingPreset),
+        Custom(CustomFileNaming),
+    }
+    impl Default for FileNamingOption {
         fn default() -> Self {
-            Self {
-                add_hook_usage_flag: false,
+            FileNamingOption::Preset(FileNamingPreset::default())
+        }
+    }
+
+    /// regular expression matched against function names
+    #[derive(Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(try_from = "String")]
+    pub struct NamePattern(#[schemars(with = "String")] pub Regex);
+
+    impl TryFrom<String> for NamePattern {
+        type Error = regex::Error;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            Regex::new(&value).map(NamePattern)
+        }
+    }
+
+    /// how signals are read, for detection of signal reads
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct SignalAccessors {
+        /// properties which read signals, `value` for `sig.value`
+        #[serde(default = "default_signal_properties")]
+        pub properties: Vec<String>,
+        /// methods which read signals, like `get` for `sig.get()`
+        #[serde(default)]
+        pub methods: Vec<String>,
+        /// methods and functions which read signals without tracking, like `sig.peek()` or
+        /// `untracked(() => sig.value)`. Reads inside them never count, even if the name is
+        /// listed in `properties` or `methods`
+        #[serde(default = "default_untracked_accessors")]
+        pub untracked: Vec<String>,
+    }
+    fn default_signal_properties() -> Vec<String> {
+        vec!["value".into()]
+    }
+    fn default_untracked_accessors() -> Vec<String> {
+        vec!["peek".into(), "untracked".into()]
+    }
+    impl Default for SignalAccessors {
+        fn default() -> Self {
+            SignalAccessors {
+                properties: default_signal_properties(),
+                methods: vec![],
+                untracked: default_untracked_accessors(),
             }
         }
     }
-    fn default_import_source() -> String {
-        "@preact-signals/safe-react/tracking".into()
+
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct RscConfig {
+        /// globs of client modules without `"use client"`, `**` matches across directories and
+        /// `*` within one
+        #[serde(default)]
+        pub client_files: Vec<String>,
+    }
+
+    /// React Server Components support: only `"use client"` modules are instrumented, server
+    /// modules and server actions are left as is
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum RscOption {
+        Enabled(bool),
+        Config(RscConfig),
+    }
+    impl Default for RscOption {
+        fn default() -> Self {
+            RscOption::Enabled(false)
+        }
+    }
+
+    /// manifest of exported functions reading signals, written by the native pre-pass
+    #[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct SignalManifestOptions {
+        /// path of the manifest file
+        pub path: String,
+        /// directory module keys are relative to, file paths outside of it are used as is
+        #[serde(default)]
+        pub root: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, Default)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct PreactSignalsPluginExperimental {
+        /// passes kind of the tracked function (component or hook) to `useSignals`
+        #[serde(default)]
+        pub add_hook_usage_flag: bool,
     }
     fn default_transform_hooks() -> bool {
         true
     }
 
-    #[derive(Deserialize, Debug, Clone)]
-    #[serde(rename_all = "camelCase")]
+    #[derive(Deserialize, JsonSchema, Debug, Clone)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
     pub struct PreactSignalsPluginOptions {
         #[serde(default)]
-        pub mode: TransformMode,
-        #[serde(default = "default_import_source")]
-        pub import_source: String,
+        pub mode: ModeOption,
+        #[serde(default)]
+        pub runtime: RuntimeOption,
+        /// module tracking functions are imported from, overrides the one of `runtime`
+        #[serde(default)]
+        pub import_source: Option<String>,
+        /// whether hooks (`use` prefixed functions) are tracked without `@useSignals`,
+        /// prefer `mode.hooks`
         #[serde(default = "default_transform_hooks")]
         pub transform_hooks: bool,
         #[serde(default)]
+        pub unknown_origin: UnknownOrigin,
+        #[serde(default)]
+        pub signal_accessors: SignalAccessors,
+        #[serde(default)]
+        pub hocs: HocsOption,
+        /// callee names or member paths of element factories in already compiled JSX, like
+        /// `h` or `Preact.h`. `React.createElement` and factories imported from `react`,
+        /// `react/jsx-runtime` and `react/jsx-dev-runtime` are always recognized
+        #[serde(default)]
+        pub jsx_pragmas: Vec<String>,
+        /// names of components, `^[A-Z]` by default
+        #[serde(default)]
+        pub component_name_pattern: Option<NamePattern>,
+        /// names of hooks, `^use[A-Z]` by default
+        #[serde(default)]
+        pub hook_name_pattern: Option<NamePattern>,
+        /// names of functions tracked as if they were marked with `@useSignals`, `*` matches
+        /// any characters
+        #[serde(default)]
+        pub track_names: Vec<String>,
+        /// names of functions never tracked, as if they were marked with `@noUseSignals`
+        #[serde(default)]
+        pub ignore_names: Vec<String>,
+        #[serde(default)]
+        pub file_naming: FileNamingOption,
+        #[serde(default)]
+        pub rsc: RscOption,
+        /// functions imported from other modules which read signals, used by `auto` mode
+        #[serde(default)]
+        pub signal_manifest: Option<SignalManifestOptions>,
+        #[serde(default)]
+        pub manual_use_signals: ManualUseSignals,
+        #[serde(default)]
+        pub wrap_style: WrapStyleOption,
+        #[serde(default)]
         pub experimental: PreactSignalsPluginExperimental,
     }
 
+    /// invalid plugin config, with a suggestion for misspelled keys
+    #[derive(Debug)]
+    pub struct OptionsError {
+        error: serde_json::Error,
+        /// option holding an invalid nested object, which serde can't point at
+        option: Option<&'static str>,
+        suggestion: Option<String>,
+    }
+
+    impl Display for OptionsError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if let Some(option) = self.option {
+                write!(f, "invalid `{option}`: ")?;
+            }
+            self.error.fmt(f)?;
+            if let Some(suggestion) = &self.suggestion {
+                write!(f, ", did you mean `{suggestion}`?")?;
+            }
+            Ok(())
+        }
+    }
+
+    // serde reports unknown fields as "unknown field `x`, expected one of `a`, `b`", and
+    // unknown variants the same way
+    fn suggest_field(message: &str) -> Option<String> {
+        static UNKNOWN_FIELD: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r#"^unknown (?:field|variant) `([^`]*)`, expected (.*)"#).unwrap()
+        });
+        static FIELD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"`([^`]*)`"#).unwrap());
+
+        let captures = UNKNOWN_FIELD.captures(message)?;
+        let unknown = captures.get(1)?.as_str().to_lowercase();
+        FIELD
+            .captures_iter(captures.get(2)?.as_str())
+            .filter_map(|it| it.get(1))
+            .map(|it| it.as_str())
+            .map(|it| (it, strsim::levenshtein(&unknown, &it.to_lowercase())))
+            .filter(|(it, distance)| *distance <= it.len().div_ceil(3))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(it, _)| it.to_owned())
+    }
+
+    type UntaggedCheck = fn(&Value) -> Option<serde_json::Error>;
+
+    /// error of the variant an untagged enum value was meant to be, a string for presets and an
+    /// object for configs
+    fn untagged_error<Preset, Config>(value: &Value) -> Option<serde_json::Error>
+    where
+        Preset: DeserializeOwned,
+        Config: DeserializeOwned,
+    {
+        match value {
+            Value::Object(_) => serde_json::from_value::<Config>(value.clone()).err(),
+            _ => serde_json::from_value::<Preset>(value.clone()).err(),
+        }
+    }
+
+    /// serde only reports that no variant of an untagged enum matched, so options holding
+    /// one are checked against the variant they were meant to be
+    fn nested_error(json: &str) -> Option<(&'static str, serde_json::Error)> {
+        let options = serde_json::from_str::<Value>(json).ok()?;
+        let checks: [(&'static str, UntaggedCheck); 6] = [
+            ("mode", untagged_error::<TransformMode, KindModes>),
+            ("runtime", untagged_error::<RuntimePreset, CustomRuntime>),
+            ("wrapStyle", untagged_error::<WrapStyle, WrapStyles>),
+            (
+                "fileNaming",
+                untagged_error::<FileNamingPreset, CustomFileNaming>,
+            ),
+            ("rsc", untagged_error::<bool, RscConfig>),
+            ("hocs", |value| match value {
+                Value::Array(hocs) => hocs.iter().find_map(untagged_error::<String, HocConfig>),
+                _ => untagged_error::<HocsPreset, HocConfig>(value),
+            }),
+        ];
+        checks.into_iter().find_map(|(option, check)| {
+            options
+                .get(option)
+                .and_then(check)
+                .map(|error| (option, error))
+        })
+    }
+
+    impl PreactSignalsPluginOptions {
+        pub fn from_json(json: &str) -> Result<PreactSignalsPluginOptions, OptionsError> {
+            serde_json::from_str(json).map_err(|error| {
+                let (option, error) = match nested_error(json) {
+                    Some((option, nested)) => (Some(option), nested),
+                    None => (None, error),
+                };
+                OptionsError {
+                    suggestion: suggest_field(&error.to_string()),
+                    option,
+                    error,
+                }
+            })
+        }
+    }
+
     impl Default for PreactSignalsPluginOptions {
         fn default() -> Self {
             PreactSignalsPluginOptions {
-                mode: TransformMode::default(),
-                import_source: default_import_source(),
+                mode: ModeOption::default(),
+                runtime: RuntimeOption::default(),
+                import_source: None,
                 transform_hooks: default_transform_hooks(),
+                unknown_origin: UnknownOrigin::default(),
+                signal_accessors: SignalAccessors::default(),
+                hocs: HocsOption::default(),
+                jsx_pragmas: vec![],
+                component_name_pattern: None,
+                hook_name_pattern: None,
+                track_names: vec![],
+                ignore_names: vec![],
+                file_naming: FileNamingOption::default(),
+                rsc: RscOption::default(),
+                signal_manifest: None,
+                manual_use_signals: ManualUseSignals::default(),
+                wrap_style: WrapStyleOption::default(),
                 experimental: PreactSignalsPluginExperimental::default(),
             }
         }
@@ -130,17 +684,45 @@ pub mod options {
     impl PreactSignalsPluginOptions {
         pub fn auto_hooks() -> PreactSignalsPluginOptions {
             PreactSignalsPluginOptions {
-                mode: TransformMode::Auto,
-                import_source: default_import_source(),
+                mode: ModeOption::Mode(TransformMode::Auto),
+                runtime: RuntimeOption::default(),
+                import_source: None,
                 transform_hooks: true,
+                unknown_origin: UnknownOrigin::default(),
+                signal_accessors: SignalAccessors::default(),
+                hocs: HocsOption::default(),
+                jsx_pragmas: vec![],
+                component_name_pattern: None,
+                hook_name_pattern: None,
+                track_names: vec![],
+                ignore_names: vec![],
+                file_naming: FileNamingOption::default(),
+                rsc: RscOption::default(),
+                signal_manifest: None,
+                manual_use_signals: ManualUseSignals::default(),
+                wrap_style: WrapStyleOption::default(),
                 experimental: PreactSignalsPluginExperimental::default(),
             }
         }
         pub fn auto_hooks_and_hook_usage_flag() -> PreactSignalsPluginOptions {
             PreactSignalsPluginOptions {
-                mode: TransformMode::Auto,
-                import_source: default_import_source(),
+                mode: ModeOption::Mode(TransformMode::Auto),
+                runtime: RuntimeOption::default(),
+                import_source: None,
                 transform_hooks: true,
+                unknown_origin: UnknownOrigin::default(),
+                signal_accessors: SignalAccessors::default(),
+                hocs: HocsOption::default(),
+                jsx_pragmas: vec![],
+                component_name_pattern: None,
+                hook_name_pattern: None,
+                track_names: vec![],
+                ignore_names: vec![],
+                file_naming: FileNamingOption::default(),
+                rsc: RscOption::default(),
+                signal_manifest: None,
+                manual_use_signals: ManualUseSignals::default(),
+                wrap_style: WrapStyleOption::default(),
                 experimental: PreactSignalsPluginExperimental {
                     add_hook_usage_flag: true,
                 },
@@ -149,9 +731,23 @@ pub mod options {
 
         pub fn auto_hooks_context_flags() -> PreactSignalsPluginOptions {
             PreactSignalsPluginOptions {
-                mode: TransformMode::Auto,
-                import_source: default_import_source(),
+                mode: ModeOption::Mode(TransformMode::Auto),
+                runtime: RuntimeOption::default(),
+                import_source: None,
                 transform_hooks: true,
+                unknown_origin: UnknownOrigin::default(),
+                signal_accessors: SignalAccessors::default(),
+                hocs: HocsOption::default(),
+                jsx_pragmas: vec![],
+                component_name_pattern: None,
+                hook_name_pattern: None,
+                track_names: vec![],
+                ignore_names: vec![],
+                file_naming: FileNamingOption::default(),
+                rsc: RscOption::default(),
+                signal_manifest: None,
+                manual_use_signals: ManualUseSignals::default(),
+                wrap_style: WrapStyleOption::default(),
                 experimental: PreactSignalsPluginExperimental {
                     add_hook_usage_flag: true,
                 },
@@ -159,20 +755,50 @@ pub mod options {
         }
     }
 }
-use options::{PreactSignalsPluginOptions, TransformMode};
+use options::{
+    KindMode, KindModes, ManualUseSignals, ModeOption, PreactSignalsPluginOptions,
+    SignalManifestOptions, TransformMode, UnknownKind, UnknownOrigin, WrapStyle, WrapStyles,
+};
 
 pub struct SignalsTransformVisitor<C>
 where
     C: Comments + Debug,
 {
     comments: C,
-    mode: TransformMode,
+    modes: KindModes,
     import_use_signals: Option<Ident>,
-    use_signals_import_source: Str,
+    import_track_class_signals: Option<Ident>,
+    runtime: Runtime,
     ignore_span: Option<Span>,
-    file_trackable_name: Option<Trackable>,
+    file_path: Option<String>,
+    /// name of anonymous default exports
+    file_name: Option<FileName>,
+    naming: Naming,
     transform_hooks: bool,
     add_context_to_hooks: bool,
+    signal_bindings: SignalBindings,
+    call_graph: CallGraph,
+    signal_manifest: Option<SignalManifestOptions>,
+    /// loaded on the first file
+    manifest: Option<SignalManifest>,
+    jsx_factories: JsxFactories,
+    unknown_origin: UnknownOrigin,
+    accessors: Accessors,
+    rsc: Option<Rsc>,
+    /// server component modules, where hooks can't be called
+    server_module: bool,
+    hocs: Hocs,
+    manual_use_signals: ManualUseSignals,
+    wrap_styles: WrapStyles,
+    /// `prepend` was configured, but the runtime doesn't finish stores by itself
+    unfinished_prepend: bool,
+    /// local names of `useSignals` which may be called by hand
+    hand_written_use_signals: Vec<Id>,
+    /// members of the innermost class are members of a class component,
+    /// instance members of which are called by `render` and can't call hooks
+    in_component_class: bool,
+    /// positions which leading comments were checked for `@useSignals`/`@noUseSignals`
+    attached_directives: HashSet<BytePos>,
     // unresolved_mark: Mark,
 }
 impl<C> SignalsTransformVisitor<C>
@@ -181,62 +807,223 @@ where
 {
     fn get_import_use_signals(&mut self) -> Ident {
         self.import_use_signals
-            .get_or_insert(private_ident!("_useSignals"))
+            .get_or_insert_with(|| self.runtime.use_signals_local())
+            .clone()
+    }
+    /// local names of `useSignals` and `trackClassSignals`, used to detect instrumented functions.
+    /// Hand-written `useSignals` is left to `find_manual_call`, even if it's reused by the plugin
+    fn tracking_ids(&self) -> Vec<Id> {
+        [&self.import_use_signals, &self.import_track_class_signals]
+            .into_iter()
+            .flatten()
+            .map(|it| it.to_id())
+            .filter(|it| !self.hand_written_use_signals.contains(it))
+            .collect()
+    }
+    /// `useSignals` the module already imports, so rewritten functions keep using its runtime
+    fn rewritten_use_signals(&self) -> Option<Ident> {
+        if self.manual_use_signals != ManualUseSignals::Rewrite
+            || !self.runtime.calls_like_presets()
+        {
+            return None;
+        }
+        self.hand_written_use_signals
+            .first()
+            .map(|it| Ident::from(it.clone()))
+    }
+    /// file mode applies to all kinds, `unknownKind` and `transformHooks` are kept
+    fn set_file_mode(&mut self, mode: TransformMode) {
+        self.modes = KindModes {
+            unknown_kind: self.modes.unknown_kind,
+            ..ModeOption::Mode(mode).modes(self.transform_hooks)
+        };
+    }
+    fn kind_mode(&self, trackable: &Trackable) -> KindMode {
+        match trackable {
+            Trackable::Component => self.modes.components,
+            Trackable::Hook => self.modes.hooks,
+            Trackable::Unknown => KindMode::Manual,
+        }
+    }
+    fn get_import_track_class_signals(&mut self) -> Ident {
+        // namespace import provides both functions
+        if self.runtime.is_namespace() {
+            return self.get_import_use_signals();
+        }
+        self.import_track_class_signals
+            .get_or_insert_with(|| self.runtime.track_class_signals_local())
             .clone()
     }
     pub fn from_options(
         options: PreactSignalsPluginOptions,
         comments: C,
-        file_trackable_name: Option<Trackable>,
+        file_path: Option<&str>,
         // unresolved_mark: Mark,
     ) -> Self {
+        let naming = Naming::from_options(&options);
+        let runtime = Runtime::from_options(&options.runtime, options.import_source.as_deref());
+        let wrap_styles = options.wrap_style.styles();
+        let unfinished_prepend = wrap_styles.has_prepend() && !runtime.finishes_stores();
         SignalsTransformVisitor {
             comments,
-            file_trackable_name,
-            mode: options.mode,
+            file_path: file_path.map(str::to_owned),
+            file_name: file_path.and_then(|it| naming.file_name(it)),
+            naming,
+            modes: options.mode.modes(options.transform_hooks),
             import_use_signals: None,
-            use_signals_import_source: Str::from_str(options.import_source.as_str()),
+            import_track_class_signals: None,
+            runtime,
             transform_hooks: options.transform_hooks,
             ignore_span: None,
             add_context_to_hooks: options.experimental.add_hook_usage_flag,
+            signal_bindings: SignalBindings::default(),
+            call_graph: CallGraph::default(),
+            signal_manifest: options.signal_manifest.clone(),
+            manifest: None,
+            jsx_factories: JsxFactories::new(&options.jsx_pragmas),
+            unknown_origin: options.unknown_origin,
+            accessors: Accessors::from_options(&options.signal_accessors),
+            rsc: Rsc::from_options(&options.rsc),
+            server_module: false,
+            hocs: Hocs::from_option(&options.hocs),
+            manual_use_signals: options.manual_use_signals,
+            wrap_styles: if unfinished_prepend {
+                wrap_styles.without_prepend()
+            } else {
+                wrap_styles
+            },
+            unfinished_prepend,
+            in_component_class: false,
+            hand_written_use_signals: vec![],
+            attached_directives: HashSet::new(),
             // unresolved_mark,
             // context_mark: unresolved_mark,
         }
     }
     pub fn from_default(
         comments: C,
-        file_trackable_name: Option<Trackable>,
+        file_path: Option<&str>,
         // unresolved_mark: Mark,
     ) -> Self {
         SignalsTransformVisitor::from_options(
             PreactSignalsPluginOptions::default(),
             comments,
-            file_trackable_name,
+            file_path,
             // unresolved_mark,
         )
     }
 
-    fn process_var_decl<T>(&mut self, n: &mut VarDecl, additional_spans: Option<&[&Span]>) {
-        if let Some(first) = n.decls.as_mut_slice().first_mut()
-            && let Some(init) = &mut first.init
-            && let child_span = init.unwrap_parens().get_span().clone()
-            && let Some(mut component) = extract_fn_from_expr(init.unwrap_parens_mut())
-            && let defaults_spans = &[&child_span, &n.span]
-            && let spans = if let Some(extra_spans) = additional_spans {
-                [defaults_spans, extra_spans].concat()
-            } else {
-                defaults_spans.to_vec()
+    fn process_var_decl(&mut self, n: &mut VarDecl, additional_spans: Option<&[&Span]>) {
+        let decl_span = n.span;
+        // directives on the declaration apply to every declarator
+        for declarator in n.decls.iter_mut() {
+            if let Some(init) = &mut declarator.init
+                && let Some(ClassExpr { ident, class }) = extract_class_from_expr(init, &self.hocs)
+            {
+                let name = ident.as_ref().or(match &declarator.name {
+                    Pat::Ident(binding) => Some(&binding.id),
+                    _ => None,
+                });
+                let defaults_spans = &[&declarator.span, &decl_span];
+                let spans = if let Some(extra_spans) = additional_spans {
+                    [defaults_spans, extra_spans].concat()
+                } else {
+                    defaults_spans.to_vec()
+                };
+                self.process_class(class, name, &spans, false);
+                continue;
             }
-            && let Some(trackable) = match component.get_fn_ident() {
-                None => {
-                    self.should_track_option_ident(&spans, Some(&first.name), &component, false)
+            if let Some(init) = &mut declarator.init
+                && let child_span = *init.unwrap_parens().get_span()
+                && let Some(mut component) = extract_fn_from_expr(init, &self.hocs)
+                && let defaults_spans = &[&child_span, &declarator.span, &decl_span]
+                && let spans = if let Some(extra_spans) = additional_spans {
+                    [defaults_spans, extra_spans].concat()
+                } else {
+                    defaults_spans.to_vec()
                 }
-                Some(ident) => {
-                    self.should_track_option_ident(&spans, Some(&ident), &component, false)
+                && let Some(trackable) = match component.get_fn_ident() {
+                    None => self.should_track_option_ident(
+                        &spans,
+                        Some(&declarator.name),
+                        &component,
+                        false,
+                    ),
+                    Some(ident) => {
+                        self.should_track_option_ident(&spans, Some(&ident), &component, false)
+                    }
                 }
+            {
+                self.track(trackable, &mut component);
             }
+        }
+    }
+}
+
+impl<C> SignalsTransformVisitor<C>
+where
+    C: Comments + Debug,
+{
+    fn process_class(
+        &mut self,
+        class: &mut Class,
+        ident: Option<&Ident>,
+        spans: &[&Span],
+        is_default_export: bool,
+    ) {
+        let extends_component = class
+            .super_class
+            .as_deref()
+            .is_some_and(is_component_super_class);
+        let class_span = class.span;
+        let Some(render) = find_render_method(class) else {
+            return;
+        };
+        let name = (ident.is_some() || extends_component).then_some(ClassComponentName {
+            ident,
+            extends_component,
+        });
+        let spans = [spans, &[&class_span, &render.span]].concat();
+
+        if self
+            .should_track_option_ident(
+                &spans,
+                name.as_ref(),
+                render.function.deref(),
+                is_default_export,
+            )
+            .is_none()
         {
-            self.track(trackable, &mut component);
+            return;
+        }
+        if !self.runtime.tracks_classes() {
+            diagnostics::class_not_tracked_by_runtime(
+                ident.map_or(render.key.span(), |it| it.span),
+            );
+            return;
+        }
+        if let Some(body) = &mut render.function.body {
+            let local = self.get_import_track_class_signals();
+            if let Some(track_call) = self.runtime.track_class_signals_call(local) {
+                body.stmts = wrap_with_class_signals(
+                    &body.stmts,
+                    track_call,
+                    self.wrap_styles.components,
+                    self.runtime.finish(),
+                );
+            }
+        }
+        // subscribed on commit, so renders which are never committed don't subscribe
+        for (lifecycle, method) in [("componentDidMount", "m"), ("componentWillUnmount", "u")] {
+            let local = self.get_import_track_class_signals();
+            if let Some(call) = self.runtime.class_lifecycle_call(local, method)
+                && !add_lifecycle_call(class, lifecycle, call)
+            {
+                diagnostics::class_lifecycle_not_wired(
+                    ident.map_or(class_span, |it| it.span),
+                    lifecycle,
+                );
+            }
         }
     }
 }
@@ -248,50 +1035,100 @@ enum ShouldTrack {
     Auto,
 }
 
-fn should_track_by_comment<C>(comments: &C, span: &Span) -> ShouldTrack
+/// `@useSignals`/`@noUseSignals` in leading comments of `pos`, both `/** @useSignals */` and `// @useSignals`
+fn comment_directives<C>(comments: &C, pos: BytePos) -> Vec<(ShouldTrack, Span)>
 where
     C: Comments + Debug,
 {
-    let comments = comments.get_leading(span.lo);
-    // println!(
-    //     "comments: {:?}",
-    //     comments.as_ref().map(|it| it
-    //         .iter()
-    //         .map(|it| it.text.as_str())
-    //         .collect::<Vec<_>>()
-    //         .join(", "))
-    // );
-
-    match comments {
-        Some(item) => {
-            let is_track_signals = item
-                .iter()
-                .find(|it| {
-                    it.kind == CommentKind::Block && is_track_signals_directive(it.text.as_str())
-                })
-                .is_some();
-            let is_no_track_signals = item
-                .iter()
-                .find(|it| {
-                    it.kind == CommentKind::Block && is_no_track_signals_directive(it.text.as_str())
-                })
-                .is_some();
-
-            match (is_track_signals, is_no_track_signals) {
-                (true, true) => {
-                    // TODO: warn
-                    // println!("component uses both @useSignals and @noUseSignals at the same time, ignoring @useSignals");
-                    ShouldTrack::OptOut
-                }
-                (true, false) => ShouldTrack::OptIn,
-                (false, true) => ShouldTrack::OptOut,
-                _ => ShouldTrack::Auto,
-            }
+    comments
+        .get_leading(pos)
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|it| {
+            [
+                is_track_signals_directive(it.text.as_str())
+                    .then_some((ShouldTrack::OptIn, it.span)),
+                is_no_track_signals_directive(it.text.as_str())
+                    .then_some((ShouldTrack::OptOut, it.span)),
+            ]
+        })
+        .flatten()
+        .collect()
+}
+
+/// Mode of a single file, set by `// @signalsMode auto` header comment or `"use signals auto"` prologue
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum FileMode {
+    Transform(TransformMode),
+    Off,
+}
+
+impl FileMode {
+    fn parse(value: &str) -> Option<FileMode> {
+        match value {
+            "manual" => Some(FileMode::Transform(TransformMode::Manual)),
+            "all" => Some(FileMode::Transform(TransformMode::All)),
+            "auto" => Some(FileMode::Transform(TransformMode::Auto)),
+            "off" => Some(FileMode::Off),
+            _ => None,
+        }
+    }
+
+    fn from_comment(text: &str) -> Option<FileMode> {
+        static RE: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"(?:\s|^|\*)@signalsMode\s+(\w+)"#).unwrap());
+
+        FileMode::parse(RE.captures(text)?.get(1)?.as_str())
+    }
+
+    fn from_directive(directive: &str) -> Option<FileMode> {
+        match directive {
+            TRACK_SIGNALS_BODY_DIRECTIVE => Some(FileMode::Transform(TransformMode::All)),
+            NO_TRACK_SIGNALS_BODY_DIRECTIVE => Some(FileMode::Off),
+            _ => FileMode::parse(directive.strip_prefix("use signals ")?),
         }
-        None => ShouldTrack::Auto,
     }
 }
 
+fn file_mode<'a, C>(
+    comments: &C,
+    start: BytePos,
+    mut directives: impl Iterator<Item = &'a Str>,
+) -> Option<FileMode>
+where
+    C: Comments + Debug,
+{
+    directives
+        .find_map(|it| FileMode::from_directive(it.value.as_str()))
+        .or_else(|| {
+            comments
+                .get_leading(start)?
+                .iter()
+                .find_map(|it| FileMode::from_comment(it.text.as_str()))
+        })
+}
+
+const TRACK_SIGNALS_BODY_DIRECTIVE: &str = "use signals";
+const NO_TRACK_SIGNALS_BODY_DIRECTIVE: &str = "use no signals";
+/// Reanimated worklets
+const WORKLET_DIRECTIVE: &str = "worklet";
+
+fn body_directives<Comp>(component: &Comp) -> impl Iterator<Item = (ShouldTrack, Span)>
+where
+    Comp: Detectable,
+{
+    [
+        component
+            .find_directive(TRACK_SIGNALS_BODY_DIRECTIVE)
+            .map(|span| (ShouldTrack::OptIn, span)),
+        component
+            .find_directive(NO_TRACK_SIGNALS_BODY_DIRECTIVE)
+            .map(|span| (ShouldTrack::OptOut, span)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
 impl<C> SignalsTransformVisitor<C>
 where
     C: Comments + Debug,
@@ -301,89 +1138,231 @@ where
         I: MaybeComponentName,
     {
         if is_default_export && ident.is_none() {
-            self.file_trackable_name.to_owned()
+            self.file_name
+                .as_ref()
+                .and_then(|it| self.naming.file_trackable(it))
         } else {
-            ident.and_then(|it| it.is_trackable())
+            ident.and_then(|it| it.is_trackable(&self.naming))
+        }
+    }
+
+    /// bindings imported from functions the signal manifest lists
+    fn imported_readers<N>(&mut self, n: &N) -> HashSet<Id>
+    where
+        N: for<'a> VisitWith<ImportedReaders<'a>>,
+    {
+        let Some(options) = &self.signal_manifest else {
+            return HashSet::new();
+        };
+        let manifest = self.manifest.get_or_insert_with(|| {
+            SignalManifest::load(Path::new(&options.path)).unwrap_or_else(|error| {
+                diagnostics::manifest_unreadable(&error);
+                SignalManifest::default()
+            })
+        });
+        manifest.imported_readers(n, self.file_path.as_deref(), options.root.as_deref())
+    }
+
+    /// render phase signal reads of `auto` mode, including reads in local hooks and helpers
+    /// the function calls
+    fn reads_signals<D: Detectable>(&self, component: &D) -> bool {
+        component.has_signal_read(&self.signal_bindings, &self.accessors, self.unknown_origin)
+            || self
+                .call_graph
+                .calls_signal_reader(&component.render_calls())
+    }
+
+    /// render phase JSX of `auto` mode, including JSX of local helpers the function calls
+    fn renders_jsx<D: Detectable>(&self, component: &D) -> bool {
+        component.has_render_jsx(&self.jsx_factories)
+            || self.call_graph.calls_renderer(&component.render_calls())
+    }
+
+    /// `trackNames`/`ignoreNames` decision for the function
+    fn forced_by_name<I>(&self, ident: Option<&I>, is_default_export: bool) -> Option<ShouldTrack>
+    where
+        I: MaybeComponentName,
+    {
+        let name = if is_default_export && ident.is_none() {
+            self.file_name.as_ref().map(|it| it.name.as_str())
+        } else {
+            ident.and_then(|it| it.component_name())
+        }?;
+        match self.naming.forced(name)? {
+            true => Some(ShouldTrack::OptIn),
+            false => Some(ShouldTrack::OptOut),
         }
     }
 
     #[inline]
     fn should_track_option_ident<I, Comp>(
-        &self,
+        &mut self,
         comment_spans: &[&Span],
         ident: Option<&I>,
         component: &Comp,
         is_default_export: bool,
     ) -> Option<Trackable>
     where
-        Comp: Detectable,
+        Comp: Detectable + FunctionLikeExpr,
         I: MaybeComponentName,
     {
-        let comments: &C = &self.comments;
-        let should_track = comment_spans
-            .into_iter()
-            .filter_map(|span| match should_track_by_comment(comments, span) {
-                ShouldTrack::Auto => None,
-                it => Some(it),
-            })
-            .reduce(|acc, it| match (acc, it) {
-                (ShouldTrack::OptIn, ShouldTrack::OptOut) => ShouldTrack::OptOut,
-                (ShouldTrack::OptOut, ShouldTrack::OptIn) => ShouldTrack::OptOut,
-                (_, it) => it,
-            })
-            .unwrap_or(ShouldTrack::Auto);
+        let mut directives = vec![];
+        for span in comment_spans {
+            let found = comment_directives(&self.comments, span.lo);
+            if !found.is_empty() {
+                self.attached_directives.insert(span.lo);
+            }
+            directives.extend(found);
+        }
+        directives.extend(body_directives(component));
+
+        if component
+            .body_stmts()
+            .is_some_and(|it| is_instrumented(it, &self.tracking_ids(), self.runtime.finish()))
+        {
+            return None;
+        }
+
+        let find = |kind: ShouldTrack| {
+            directives
+                .iter()
+                .find(|(it, _)| *it == kind)
+                .map(|(_, span)| *span)
+        };
+        if self.server_module {
+            if let Some(opt_in) = find(ShouldTrack::OptIn) {
+                diagnostics::opt_in_server_module(opt_in);
+            }
+            return None;
+        }
+        // worklets run on the UI thread, where hooks can't be called
+        let is_worklet = component.find_directive(WORKLET_DIRECTIVE).is_some();
+        let is_server_action =
+            self.rsc.is_some() && component.find_directive(USE_SERVER_DIRECTIVE).is_some();
+        let should_track = match (find(ShouldTrack::OptIn), find(ShouldTrack::OptOut)) {
+            (Some(opt_in), Some(opt_out)) => {
+                diagnostics::conflicting_directives(opt_in, opt_out);
+                ShouldTrack::OptOut
+            }
+            (Some(opt_in), None) if is_worklet => {
+                diagnostics::opt_in_worklet(opt_in);
+                ShouldTrack::OptOut
+            }
+            (None, None) if is_worklet => ShouldTrack::OptOut,
+            (Some(opt_in), None) if is_server_action => {
+                diagnostics::opt_in_server_action(opt_in);
+                ShouldTrack::OptOut
+            }
+            (None, None) if is_server_action => ShouldTrack::OptOut,
+            (Some(opt_in), None) if !component.is_regular() => {
+                diagnostics::opt_in_not_regular_function(opt_in);
+                ShouldTrack::OptOut
+            }
+            (Some(_), None) => ShouldTrack::OptIn,
+            (None, Some(_)) => ShouldTrack::OptOut,
+            (None, None) if !component.is_regular() => ShouldTrack::OptOut,
+            (None, None) => self
+                .forced_by_name(ident, is_default_export)
+                .unwrap_or(ShouldTrack::Auto),
+        };
+        let should_track = match component
+            .body_stmts()
+            .and_then(|it| find_manual_call(it, &self.hand_written_use_signals))
+        {
+            Some(_) if should_track == ShouldTrack::OptOut => ShouldTrack::OptOut,
+            Some(call)
+                if self.manual_use_signals == ManualUseSignals::Rewrite
+                    && component.body_stmts().is_some_and(|it| {
+                        remove_manual_tracking(it, &self.hand_written_use_signals).is_some()
+                    }) =>
+            {
+                diagnostics::manual_use_signals_rewritten(call);
+                ShouldTrack::OptIn
+            }
+            Some(call) => {
+                diagnostics::manual_use_signals_skipped(call);
+                ShouldTrack::OptOut
+            }
+            None => should_track,
+        };
 
         match should_track {
             ShouldTrack::Auto => {
-                let this = &self;
-                match this.mode {
-                    TransformMode::Manual => None,
-                    TransformMode::Auto => match this.is_trackable(ident, is_default_export) {
+                let trackable = self.is_trackable(ident, is_default_export)?;
+                match (self.kind_mode(&trackable), trackable) {
+                    (KindMode::Auto, Trackable::Hook) if self.reads_signals(component) => {
                         Some(Trackable::Hook)
-                            if this.transform_hooks && component.has_dot_value() =>
-                        {
-                            Some(Trackable::Hook)
-                        }
+                    }
+                    (KindMode::Auto, Trackable::Component)
+                        if self.renders_jsx(component) && self.reads_signals(component) =>
+                    {
                         Some(Trackable::Component)
-                            if component.has_jsx() && component.has_dot_value() =>
-                        {
-                            Some(Trackable::Component)
-                        }
-                        _ => None,
-                    },
-                    TransformMode::All => match this.is_trackable(ident, is_default_export) {
+                    }
+                    (KindMode::All, Trackable::Hook)
+                        if component.has_dot_value(&self.accessors) =>
+                    {
                         Some(Trackable::Hook)
-                            if this.transform_hooks && component.has_dot_value() =>
-                        {
-                            Some(Trackable::Hook)
-                        }
-                        Some(Trackable::Component) if component.has_jsx() => {
-                            Some(Trackable::Component)
-                        }
-                        _ => None,
+                    }
+                    (KindMode::All, Trackable::Component)
+                        if component.has_jsx(&self.jsx_factories) =>
+                    {
+                        Some(Trackable::Component)
+                    }
+                    _ => None,
+                }
+            }
+            ShouldTrack::OptIn => {
+                let trackable = self.is_trackable(ident, is_default_export).unwrap_or(
+                    match self.modes.unknown_kind {
+                        UnknownKind::Unknown => Trackable::Unknown,
+                        UnknownKind::Component => Trackable::Component,
+                        UnknownKind::Hook => Trackable::Hook,
                     },
+                );
+                if self.kind_mode(&trackable) == KindMode::Off {
+                    if let Some(opt_in) = find(ShouldTrack::OptIn) {
+                        diagnostics::opt_in_kind_off(opt_in);
+                    }
+                    return None;
                 }
+                Some(trackable)
             }
-            ShouldTrack::OptIn => Some(
-                self.is_trackable(ident, is_default_export)
-                    .unwrap_or(Trackable::Unknown),
-            ),
             ShouldTrack::OptOut => None,
         }
     }
 
+    /// reports `@useSignals`/`@noUseSignals` comments which weren't checked by `should_track_option_ident`
+    fn check_unattached_directives(&mut self, pos: BytePos) {
+        if self.attached_directives.insert(pos) {
+            for (_, span) in comment_directives(&self.comments, pos) {
+                diagnostics::directive_on_non_function(span);
+            }
+        }
+    }
+
     #[inline]
     fn track<TWrappable>(&mut self, trackable: Trackable, wrappable: &mut TWrappable)
     where
         TWrappable: SignalWrappable,
     {
-        wrappable.wrap_with_use_signals(
-            self.get_import_use_signals(),
-            match self.add_context_to_hooks {
-                false => None,
-                true => Some(trackable),
-            },
-        )
+        if self.manual_use_signals == ManualUseSignals::Rewrite
+            && let Some(stmts) = wrappable.body_stmts_mut()
+            && let Some(rewritten) = remove_manual_tracking(stmts, &self.hand_written_use_signals)
+        {
+            *stmts = rewritten;
+        }
+        let style = match (&trackable, self.add_context_to_hooks) {
+            // store without usage flag finishes itself
+            (Trackable::Unknown, true) => WrapStyle::Prepend,
+            (Trackable::Hook, _) => self.wrap_styles.hooks,
+            (Trackable::Component | Trackable::Unknown, _) => self.wrap_styles.components,
+        };
+        // usage flag marks the store as finished by the caller, nothing finishes it when prepended
+        let usage =
+            (self.add_context_to_hooks && style != WrapStyle::Prepend).then_some(&trackable);
+        let local = self.get_import_use_signals();
+        let hook_call = self.runtime.use_signals_call(local, usage);
+        wrappable.wrap_with_use_signals(hook_call, style, self.runtime.finish())
     }
 }
 impl<C> VisitMut for SignalsTransformVisitor<C>
@@ -399,7 +1378,7 @@ where
             .unwrap_or(true);
 
         if should_process {
-            self.process_var_decl::<SignalsTransformVisitor<C>>(n, None)
+            self.process_var_decl(n, None)
         }
 
         n.visit_mut_children_with(self);
@@ -410,12 +1389,9 @@ where
                 span,
                 decl: Decl::Var(ref mut var_decl),
             } => {
-                self.process_var_decl::<SignalsTransformVisitor<C>>(
-                    var_decl.deref_mut(),
-                    Some(&[&span]),
-                );
+                self.process_var_decl(var_decl.deref_mut(), Some(&[span]));
                 let old_span = self.ignore_span;
-                self.ignore_span = Some(var_decl.span.clone());
+                self.ignore_span = Some(var_decl.span);
                 n.visit_mut_children_with(self);
                 self.ignore_span = old_span
             }
@@ -424,7 +1400,7 @@ where
                 decl: Decl::Fn(ref mut fn_declr),
             } => {
                 self.should_track_option_ident(
-                    &[&span, &fn_declr.function.span],
+                    &[span, &fn_declr.function.span],
                     Some(&fn_declr.ident),
                     fn_declr,
                     false,
@@ -432,7 +1408,23 @@ where
                 .inspect(|trackable| self.track(trackable.clone(), &mut *fn_declr.function));
 
                 let old_span = self.ignore_span;
-                self.ignore_span = Some(fn_declr.function.span.clone());
+                self.ignore_span = Some(fn_declr.function.span);
+                n.visit_mut_children_with(self);
+                self.ignore_span = old_span
+            }
+            ExportDecl {
+                span,
+                decl: Decl::Class(ref mut class_decl),
+            } => {
+                self.process_class(
+                    &mut class_decl.class,
+                    Some(&class_decl.ident),
+                    &[span],
+                    false,
+                );
+
+                let old_span = self.ignore_span;
+                self.ignore_span = Some(class_decl.class.span);
                 n.visit_mut_children_with(self);
                 self.ignore_span = old_span
             }
@@ -446,7 +1438,7 @@ where
                 decl: DefaultDecl::Fn(ref mut fn_expr),
             } => {
                 if let Some(trackable) = self.should_track_option_ident(
-                    &[&span, &fn_expr.function.span],
+                    &[span, &fn_expr.function.span],
                     fn_expr.ident.as_ref(),
                     fn_expr,
                     true,
@@ -454,20 +1446,34 @@ where
                     self.track(trackable, &mut *fn_expr.function);
                 }
                 let old_span = self.ignore_span;
-                self.ignore_span = Some(fn_expr.function.span.clone());
+                self.ignore_span = Some(fn_expr.function.span);
                 n.visit_mut_children_with(self);
                 self.ignore_span = old_span
             }
+            ExportDefaultDecl {
+                span,
+                decl: DefaultDecl::Class(ref mut class_expr),
+            } => {
+                self.process_class(
+                    &mut class_expr.class,
+                    class_expr.ident.as_ref(),
+                    &[span],
+                    true,
+                );
+                n.visit_mut_children_with(self);
+            }
             n => n.visit_mut_children_with(self),
         }
     }
     fn visit_mut_export_default_expr(&mut self, n: &mut ExportDefaultExpr) {
         let ExportDefaultExpr { ref mut expr, span } = n;
-        let child_span = expr.unwrap_parens().get_span().clone();
+        let child_span = *expr.unwrap_parens().get_span();
 
-        if let Some(mut component) = extract_fn_from_expr(expr.unwrap_parens_mut()) {
+        if let Some(ClassExpr { ident, class }) = extract_class_from_expr(expr, &self.hocs) {
+            self.process_class(class, ident.as_ref(), &[span, &child_span], true);
+        } else if let Some(mut component) = extract_fn_from_expr(expr, &self.hocs) {
             self.should_track_option_ident(
-                &[&span, &child_span],
+                &[span, &child_span],
                 component.get_fn_ident().as_ref(),
                 &component,
                 true,
@@ -492,15 +1498,41 @@ where
         n.visit_mut_children_with(self);
     }
 
+    fn visit_mut_class_decl(&mut self, n: &mut ClassDecl) {
+        if match self.ignore_span {
+            Some(span) => !span.eq(&n.class.span),
+            None => true,
+        } {
+            self.process_class(&mut n.class, Some(&n.ident), &[], false);
+        }
+        n.visit_mut_children_with(self);
+    }
+
     fn visit_mut_assign_expr(&mut self, n: &mut AssignExpr) {
-        if let Some(mut component) = extract_fn_from_expr(n.right.borrow_mut())
+        // `module.exports = ...` is named after the file, as `export default`
+        let is_default_export = is_cjs_default_export(&n.left);
+        if let Some(ClassExpr { ident, class }) = extract_class_from_expr(&mut n.right, &self.hocs)
+        {
+            // `Bar = class ..`, classes assigned to members need their own name or `Component`
+            let name = ident.as_ref().or(match &n.left {
+                AssignTarget::Simple(SimpleAssignTarget::Ident(binding)) => Some(&binding.id),
+                _ => None,
+            });
+            self.process_class(class, name, &[&n.span], is_default_export);
+        } else if let Some(mut component) = extract_fn_from_expr(n.right.borrow_mut(), &self.hocs)
             && let Some(trackable) = match component.get_fn_ident() {
+                None if is_default_export => {
+                    self.should_track_option_ident::<Ident, _>(&[&n.span], None, &component, true)
+                }
                 None => {
                     self.should_track_option_ident(&[&n.span], Some(&n.left), &component, false)
                 }
-                Some(ident) => {
-                    self.should_track_option_ident(&[&n.span], Some(&ident), &component, false)
-                }
+                Some(ident) => self.should_track_option_ident(
+                    &[&n.span],
+                    Some(&ident),
+                    &component,
+                    is_default_export,
+                ),
             }
         {
             self.track(trackable, &mut component);
@@ -509,7 +1541,7 @@ where
         n.visit_mut_children_with(self);
     }
     fn visit_mut_key_value_prop(&mut self, n: &mut KeyValueProp) {
-        if let Some(mut component) = extract_fn_from_expr(&mut n.value)
+        if let Some(mut component) = extract_fn_from_expr(&mut n.value, &self.hocs)
             && let Some(trackable) = self.should_track_option_ident(
                 &[n.key.get_span()],
                 Some(
@@ -538,40 +1570,221 @@ where
 
         n.visit_mut_children_with(self);
     }
+    fn visit_mut_class(&mut self, n: &mut Class) {
+        let in_component_class = mem::replace(
+            &mut self.in_component_class,
+            n.super_class
+                .as_deref()
+                .is_some_and(is_component_super_class),
+        );
+        n.visit_mut_children_with(self);
+        self.in_component_class = in_component_class;
+    }
+    fn visit_mut_class_prop(&mut self, n: &mut ClassProp) {
+        if (n.is_static || !self.in_component_class)
+            && let Some(value) = &mut n.value
+            && let Some(mut component) = extract_fn_from_expr(value, &self.hocs)
+            && let Some(trackable) = self.should_track_option_ident(
+                &[&n.span, n.key.get_span()],
+                Some(
+                    &component
+                        .get_fn_ident()
+                        .map_or(n.key.clone(), |it| PropName::Ident(it.into())),
+                ),
+                &component,
+                false,
+            )
+        {
+            self.track(trackable, &mut component);
+        }
 
-    fn visit_mut_module(&mut self, n: &mut Module) {
-        self.import_use_signals = None;
         n.visit_mut_children_with(self);
-        if let Some(ident) = &self.import_use_signals {
-            prepend_stmt(
-                &mut n.body,
-                ModuleItem::ModuleDecl(
-                    add_import(
-                        ident.clone(),
-                        self.use_signals_import_source.clone(),
-                        Some(Ident::use_signals(ident.ctxt)),
-                    )
-                    .into(),
+    }
+    fn visit_mut_private_prop(&mut self, n: &mut PrivateProp) {
+        if (n.is_static || !self.in_component_class)
+            && let Some(value) = &mut n.value
+            && let Some(mut component) = extract_fn_from_expr(value, &self.hocs)
+            && let Some(trackable) = match component.get_fn_ident() {
+                None => self.should_track_option_ident(
+                    &[&n.span, &n.key.span],
+                    Some(&n.key),
+                    &component,
+                    false,
+                ),
+                Some(ident) => self.should_track_option_ident(
+                    &[&n.span, &n.key.span],
+                    Some(&ident),
+                    &component,
+                    false,
                 ),
+            }
+        {
+            self.track(trackable, &mut component);
+        }
+
+        n.visit_mut_children_with(self);
+    }
+    fn visit_mut_class_method(&mut self, n: &mut ClassMethod) {
+        // `render` of class components is handled by `process_class`,
+        // other instance methods of class components are called by it
+        let is_render =
+            !n.is_static && matches!(&n.key, PropName::Ident(key) if key.sym == "render");
+        if !is_render
+            && (n.is_static || !self.in_component_class)
+            && n.kind == MethodKind::Method
+            && let Some(trackable) = self.should_track_option_ident(
+                &[&n.span, &n.function.span, n.key.get_span()],
+                Some(&n.key),
+                n.function.deref(),
+                false,
             )
+        {
+            self.track(trackable, &mut *n.function);
         }
+
+        n.visit_mut_children_with(self);
     }
+    fn visit_mut_private_method(&mut self, n: &mut PrivateMethod) {
+        if (n.is_static || !self.in_component_class)
+            && n.kind == MethodKind::Method
+            && let Some(trackable) = self.should_track_option_ident(
+                &[&n.span, &n.function.span, &n.key.span],
+                Some(&n.key),
+                n.function.deref(),
+                false,
+            )
+        {
+            self.track(trackable, &mut *n.function);
+        }
 
-    fn visit_mut_script(&mut self, n: &mut Script) {
-        self.import_use_signals = None;
         n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module_item(&mut self, n: &mut ModuleItem) {
+        n.visit_mut_children_with(self);
+        self.check_unattached_directives(n.span_lo());
+    }
+    fn visit_mut_stmt(&mut self, n: &mut Stmt) {
+        n.visit_mut_children_with(self);
+        self.check_unattached_directives(n.span_lo());
+    }
+    fn visit_mut_class_member(&mut self, n: &mut ClassMember) {
+        n.visit_mut_children_with(self);
+        self.check_unattached_directives(n.span_lo());
+    }
+    fn visit_mut_prop(&mut self, n: &mut Prop) {
+        n.visit_mut_children_with(self);
+        self.check_unattached_directives(n.span_lo());
+    }
 
-        if let Some(ident) = &self.import_use_signals {
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        // already transformed files (libraries compiled with this plugin) reuse their imports
+        let (existing_use_signals, existing_track_class_signals) =
+            self.runtime.find_imports(&n.body);
+        self.hand_written_use_signals = manual_tracking::collect_module_use_signals(
+            &n.body,
+            self.runtime.source(),
+            &self.runtime.use_signals_local().sym,
+        );
+        let existing_use_signals = existing_use_signals.or_else(|| self.rewritten_use_signals());
+        self.import_use_signals = existing_use_signals.clone();
+        self.import_track_class_signals = existing_track_class_signals.clone();
+        self.attached_directives.clear();
+        if self.unfinished_prepend {
+            diagnostics::prepend_not_finished();
+        }
+        self.server_module = self.rsc.as_ref().is_some_and(|it| {
+            !it.is_client_module(module_directives(&n.body), self.file_path.as_deref())
+        });
+        let modes = self.modes;
+        match file_mode(&self.comments, n.span.lo, module_directives(&n.body)) {
+            Some(FileMode::Off) => return,
+            Some(FileMode::Transform(file_mode)) => self.set_file_mode(file_mode),
+            None => {}
+        }
+        self.signal_bindings = SignalBindings::collect(n);
+        let imported = self.imported_readers(n);
+        self.jsx_factories.collect(n);
+        self.call_graph = CallGraph::collect(
+            n,
+            &self.signal_bindings,
+            &self.accessors,
+            self.unknown_origin,
+            &self.jsx_factories,
+            imported,
+        );
+        n.visit_mut_children_with(self);
+        self.modes = modes;
+        if self.manual_use_signals == ManualUseSignals::Rewrite {
+            manual_tracking::remove_unused_imports(&mut n.body, &self.hand_written_use_signals);
+        }
+        if let Some(ident) = &self.import_use_signals
+            && existing_use_signals.is_none()
+        {
             prepend_stmt(
                 &mut n.body,
-                add_require(
-                    ident.clone(),
-                    self.use_signals_import_source.clone(),
-                    Some(Ident::use_signals(ident.ctxt)),
-                    ident.ctxt,
-                ),
+                ModuleItem::ModuleDecl(self.runtime.use_signals_import(ident.clone()).into()),
             )
         }
+        if let Some(ident) = &self.import_track_class_signals
+            && existing_track_class_signals.is_none()
+            && let Some(import) = self.runtime.track_class_signals_import(ident.clone())
+        {
+            prepend_stmt(&mut n.body, ModuleItem::ModuleDecl(import.into()))
+        }
+    }
+
+    fn visit_mut_script(&mut self, n: &mut Script) {
+        // already transformed files (libraries compiled with this plugin) reuse their imports
+        let (existing_use_signals, existing_track_class_signals) =
+            self.runtime.find_requires(&n.body);
+        self.hand_written_use_signals = manual_tracking::collect_script_use_signals(
+            &n.body,
+            self.runtime.source(),
+            &self.runtime.use_signals_local().sym,
+        );
+        let existing_use_signals = existing_use_signals.or_else(|| self.rewritten_use_signals());
+        self.import_use_signals = existing_use_signals.clone();
+        self.import_track_class_signals = existing_track_class_signals.clone();
+        self.attached_directives.clear();
+        if self.unfinished_prepend {
+            diagnostics::prepend_not_finished();
+        }
+        self.server_module = self
+            .rsc
+            .as_ref()
+            .is_some_and(|it| !it.is_client_module(directives(&n.body), self.file_path.as_deref()));
+        let modes = self.modes;
+        match file_mode(&self.comments, n.span.lo, directives(&n.body)) {
+            Some(FileMode::Off) => return,
+            Some(FileMode::Transform(file_mode)) => self.set_file_mode(file_mode),
+            None => {}
+        }
+        self.signal_bindings = SignalBindings::collect(n);
+        let imported = self.imported_readers(n);
+        self.jsx_factories.collect(n);
+        self.call_graph = CallGraph::collect(
+            n,
+            &self.signal_bindings,
+            &self.accessors,
+            self.unknown_origin,
+            &self.jsx_factories,
+            imported,
+        );
+        n.visit_mut_children_with(self);
+        self.modes = modes;
+
+        if let Some(ident) = &self.import_use_signals
+            && existing_use_signals.is_none()
+        {
+            prepend_stmt(&mut n.body, self.runtime.use_signals_require(ident.clone()))
+        }
+        if let Some(ident) = &self.import_track_class_signals
+            && existing_track_class_signals.is_none()
+            && let Some(require) = self.runtime.track_class_signals_require(ident.clone())
+        {
+            prepend_stmt(&mut n.body, require)
+        }
     }
 }
 
@@ -596,38 +1809,34 @@ pub fn process_transform(
     _metadata: TransformPluginProgramMetadata,
 ) -> Program {
     // _metadata.mark
-    program.visit_mut_with(
-        &mut ({
-            let data = _metadata.get_transform_plugin_config();
-
-            let file_has_trackable_name = _metadata
-                .get_context(&TransformPluginMetadataContextKind::Filename)
-                .map(|it| PathBuf::from(it))
-                .and_then(|it| {
-                    it.file_name()
-                        .and_then(|it| it.to_str())
-                        .and_then(|it| it.is_trackable())
-                });
+    let data = _metadata.get_transform_plugin_config();
+
+    let file_path = _metadata.get_context(&TransformPluginMetadataContextKind::Filename);
+    let file_path = file_path.as_deref();
 
-            use serde_json;
-            match data {
-                Some(data) => {
-                    let options = serde_json::from_str::<PreactSignalsPluginOptions>(data.as_str())
-                        .expect("transform plugin config should be valid json");
-                    SignalsTransformVisitor::from_options(
-                        options,
-                        _metadata.comments,
-                        file_has_trackable_name,
-                        // _metadata.unresolved_mark,
-                    )
+    let mut visitor = match data {
+        Some(data) => {
+            let options = match PreactSignalsPluginOptions::from_json(data.as_str()) {
+                Ok(options) => options,
+                Err(error) => {
+                    // reported as a build error instead of a wasm panic
+                    diagnostics::invalid_config(&error);
+                    return program;
                 }
-                None => SignalsTransformVisitor::from_default(
-                    _metadata.comments,
-                    file_has_trackable_name,
-                    // _metadata.unresolved_mark,
-                ),
-            }
-        }),
-    );
+            };
+            SignalsTransformVisitor::from_options(
+                options,
+                _metadata.comments,
+                file_path,
+                // _metadata.unresolved_mark,
+            )
+        }
+        None => SignalsTransformVisitor::from_default(
+            _metadata.comments,
+            file_path,
+            // _metadata.unresolved_mark,
+        ),
+    };
+    program.visit_mut_with(&mut visitor);
     program
 }
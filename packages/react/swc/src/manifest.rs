@@ -0,0 +1,217 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use swc_core::ecma::{
+    ast::*,
+    visit::{Visit, VisitWith},
+};
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+/// Exported functions which read signals during render, by module.
+///
+/// Module keys are file paths relative to the root with `/` separators, without the extension
+/// and `/index`, so `./cart`, `./cart.js` and `./cart/index.tsx` imports share the same key.
+/// Bare specifiers (packages, path aliases) are looked up as is, the pre-pass only records files,
+/// so such keys have to be added by hand. Maps and sets are ordered, so the manifest can be
+/// checked in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SignalManifest {
+    pub version: u32,
+    pub modules: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Default for SignalManifest {
+    fn default() -> Self {
+        SignalManifest {
+            version: MANIFEST_VERSION,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, serde_json::Error),
+    Version(PathBuf, u32),
+    Parse(PathBuf, String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestError::Io(path, error) => write!(f, "can't read '{}': {error}", path.display()),
+            ManifestError::Json(path, error) => {
+                write!(f, "invalid signal manifest '{}': {error}", path.display())
+            }
+            ManifestError::Version(path, version) => write!(
+                f,
+                "signal manifest '{}' has version {version}, expected {MANIFEST_VERSION}",
+                path.display()
+            ),
+            ManifestError::Parse(path, error) => {
+                write!(f, "can't parse '{}': {error}", path.display())
+            }
+        }
+    }
+}
+
+/// `src/./cart/../cart.ts` -> `src/cart.ts`
+fn normalize(path: &str) -> String {
+    let mut segments = Vec::<&str>::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|it| *it != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// path relative to `root`, paths outside of it are used as is
+fn relative_path(file_path: &str, root: Option<&str>) -> String {
+    let path = normalize(file_path);
+    match root.map(normalize) {
+        Some(root) if !root.is_empty() => match path.strip_prefix(&root) {
+            Some(rest) if rest.starts_with('/') => rest[1..].to_owned(),
+            _ => path,
+        },
+        _ => path,
+    }
+}
+
+/// `src/cart/index.tsx` -> `src/cart`
+fn key_of_path(path: &str) -> String {
+    let path = match path.rsplit_once('.') {
+        Some((rest, extension)) if EXTENSIONS.contains(&extension) && !rest.ends_with('/') => rest,
+        _ => path,
+    };
+    match path.strip_suffix("/index") {
+        Some(rest) => rest.to_owned(),
+        None if path == "index" => String::new(),
+        None => path.to_owned(),
+    }
+}
+
+/// `src/app/../hooks/cart` for `../hooks/cart` imported by `src/app/Card.tsx`
+fn import_key(file_path: Option<&str>, root: Option<&str>, specifier: &str) -> Option<String> {
+    if !specifier.starts_with('.') {
+        return Some(specifier.to_owned());
+    }
+    let importer = relative_path(file_path?, root);
+    let dir = importer.rsplit_once('/').map_or("", |(dir, _)| dir);
+    Some(key_of_path(&normalize(&format!("{dir}/{specifier}"))))
+}
+
+impl SignalManifest {
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let json = fs::read_to_string(path).map_err(|it| ManifestError::Io(path.to_owned(), it))?;
+        let manifest = serde_json::from_str::<SignalManifest>(&json)
+            .map_err(|it| ManifestError::Json(path.to_owned(), it))?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(ManifestError::Version(path.to_owned(), manifest.version));
+        }
+        Ok(manifest)
+    }
+
+    /// pretty printed with a trailing newline
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap() + "\n"
+    }
+
+    /// key of the module at `file_path`
+    pub fn key(file_path: &str, root: Option<&str>) -> String {
+        key_of_path(&relative_path(file_path, root))
+    }
+
+    pub(crate) fn exports(
+        &self,
+        file_path: Option<&str>,
+        root: Option<&str>,
+        specifier: &str,
+    ) -> Option<&BTreeSet<String>> {
+        self.modules.get(&import_key(file_path, root, specifier)?)
+    }
+
+    /// local bindings imported from functions the manifest lists, they're signal reads for the
+    /// call graph of the file at `file_path`
+    pub fn imported_readers<N>(
+        &self,
+        n: &N,
+        file_path: Option<&str>,
+        root: Option<&str>,
+    ) -> HashSet<Id>
+    where
+        N: for<'a> VisitWith<ImportedReaders<'a>>,
+    {
+        let mut v = ImportedReaders {
+            manifest: self,
+            file_path,
+            root,
+            found: HashSet::new(),
+        };
+        n.visit_with(&mut v);
+        v.found
+    }
+}
+
+pub(crate) fn export_name(name: &ModuleExportName) -> &str {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.as_str(),
+        ModuleExportName::Str(str) => str.value.as_str(),
+    }
+}
+
+/// Collects local bindings of imports listed in the manifest
+pub struct ImportedReaders<'a> {
+    manifest: &'a SignalManifest,
+    file_path: Option<&'a str>,
+    root: Option<&'a str>,
+    found: HashSet<Id>,
+}
+
+impl Visit for ImportedReaders<'_> {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if n.type_only {
+            return;
+        }
+        let Some(exports) = self
+            .manifest
+            .exports(self.file_path, self.root, &n.src.value)
+        else {
+            return;
+        };
+        for specifier in &n.specifiers {
+            let (local, imported) = match specifier {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    local,
+                    imported,
+                    is_type_only: false,
+                    ..
+                }) => (
+                    local,
+                    imported.as_ref().map_or(local.sym.as_str(), export_name),
+                ),
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                    (local, "default")
+                }
+                _ => continue,
+            };
+            if exports.contains(imported) {
+                self.found.insert(local.to_id());
+            }
+        }
+    }
+}
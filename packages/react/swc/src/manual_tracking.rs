@@ -0,0 +1,193 @@
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::*,
+        utils::IdentUsageFinder,
+        visit::{Visit, VisitWith},
+    },
+};
+
+use crate::utils::{directives, find_import, find_require};
+
+/// modules exporting `useSignals` which is called by hand
+const USE_SIGNALS_SOURCES: &[&str] = &[
+    "@preact-signals/safe-react/tracking",
+    "@preact/signals-react/runtime",
+];
+
+/// local names of `useSignals` imported from `import_source` or other known sources.
+/// Imports named `compiled_local`, as the plugin names them, are its earlier output
+pub fn collect_module_use_signals(
+    items: &[ModuleItem],
+    import_source: &str,
+    compiled_local: &str,
+) -> Vec<Id> {
+    [import_source]
+        .into_iter()
+        .chain(USE_SIGNALS_SOURCES.iter().copied())
+        .filter_map(|source| find_import(items, source, "useSignals"))
+        .filter(|it| it.sym != compiled_local)
+        .map(|it| it.to_id())
+        .collect()
+}
+
+/// `collect_module_use_signals` for `require` calls
+pub fn collect_script_use_signals(
+    stmts: &[Stmt],
+    import_source: &str,
+    compiled_local: &str,
+) -> Vec<Id> {
+    [import_source]
+        .into_iter()
+        .chain(USE_SIGNALS_SOURCES.iter().copied())
+        .filter_map(|source| find_require(stmts, source, "useSignals"))
+        .filter(|it| it.sym != compiled_local)
+        .map(|it| it.to_id())
+        .collect()
+}
+
+/// Finds calls of `useSignals` in a function body, nested functions are separate components or
+/// callbacks, so they aren't visited
+struct FindManualCall<'a> {
+    use_signals: &'a [Id],
+    found: Option<Span>,
+}
+
+impl Visit for FindManualCall<'_> {
+    fn visit_function(&mut self, _: &Function) {}
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+    fn visit_class(&mut self, _: &Class) {}
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.found.is_some() {
+            return;
+        }
+        if let Callee::Expr(box Expr::Ident(callee)) = &n.callee
+            && self.use_signals.contains(&callee.to_id())
+        {
+            self.found = Some(n.span);
+            return;
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// span of the first hand-written `useSignals()` call
+pub fn find_manual_call(stmts: &[Stmt], use_signals: &[Id]) -> Option<Span> {
+    let mut v = FindManualCall {
+        use_signals,
+        found: None,
+    };
+    stmts.visit_with(&mut v);
+    v.found
+}
+
+fn is_use_signals_call(expr: &Expr, use_signals: &[Id]) -> bool {
+    matches!(
+        expr,
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(box Expr::Ident(callee)),
+            ..
+        }) if use_signals.contains(&callee.to_id())
+    )
+}
+
+/// `store.f()`
+fn is_finish_call(stmt: &Stmt, store: &Ident) -> bool {
+    matches!(
+        stmt,
+        Stmt::Expr(ExprStmt {
+            expr: box Expr::Call(CallExpr {
+                callee: Callee::Expr(box Expr::Member(MemberExpr {
+                    obj: box Expr::Ident(obj),
+                    prop: MemberProp::Ident(prop),
+                    ..
+                })),
+                args,
+                ..
+            }),
+            ..
+        }) if obj.to_id() == store.to_id() && prop.sym == "f" && args.is_empty()
+    )
+}
+
+/// Body without hand-written tracking, so it can be wrapped in the canonical way.
+///
+/// Supports `useSignals();` at the start of the body and
+/// `const store = useSignals(); try { ... } finally { store.f(); }`,
+/// `None` when calls are used in any other way.
+pub fn remove_manual_tracking(stmts: &[Stmt], use_signals: &[Id]) -> Option<Vec<Stmt>> {
+    let directives_count = directives(stmts).count();
+    let (prologue, body) = stmts.split_at(directives_count);
+
+    let rest = match body {
+        [Stmt::Expr(ExprStmt { expr, .. }), rest @ ..]
+            if is_use_signals_call(expr, use_signals) =>
+        {
+            rest.to_vec()
+        }
+        [Stmt::Decl(Decl::Var(var_decl)), Stmt::Try(try_stmt)] => {
+            let [VarDeclarator {
+                name: Pat::Ident(store),
+                init: Some(init),
+                ..
+            }] = var_decl.decls.as_slice()
+            else {
+                return None;
+            };
+            let TryStmt {
+                block,
+                handler: None,
+                finalizer: Some(finalizer),
+                ..
+            } = &**try_stmt
+            else {
+                return None;
+            };
+            if !is_use_signals_call(init, use_signals)
+                || !matches!(finalizer.stmts.as_slice(), [stmt] if is_finish_call(stmt, store))
+                // store is removed with the declaration
+                || IdentUsageFinder::find(&store.id, block)
+            {
+                return None;
+            }
+            block.stmts.clone()
+        }
+        _ => return None,
+    };
+    if find_manual_call(&rest, use_signals).is_some() {
+        return None;
+    }
+
+    Some([prologue, &rest].concat())
+}
+
+/// Drops imports of `useSignals` which aren't called anymore after hand-written calls were
+/// rewritten
+pub fn remove_unused_imports(items: &mut Vec<ModuleItem>, use_signals: &[Id]) {
+    let is_import =
+        |item: &ModuleItem| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)));
+    let unused = use_signals
+        .iter()
+        .filter(|id| {
+            let ident = Ident::from((*id).clone());
+            !items
+                .iter()
+                .any(|item| !is_import(item) && IdentUsageFinder::find(&ident, item))
+        })
+        .collect::<Vec<_>>();
+    if unused.is_empty() {
+        return;
+    }
+    items.retain_mut(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+            return true;
+        };
+        let len = import.specifiers.len();
+        import.specifiers.retain(|it| {
+            !matches!(it, ImportSpecifier::Named(named) if unused.contains(&&named.local.to_id()))
+        });
+        // imports without specifiers are kept unless all of them were removed
+        len == import.specifiers.len() || !import.specifiers.is_empty()
+    });
+}
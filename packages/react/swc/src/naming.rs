@@ -0,0 +1,186 @@
+use regex::Regex;
+use swc_core::common::sync::Lazy;
+
+use crate::{
+    options::{CustomFileNaming, FileNamingOption, FileNamingPreset, PreactSignalsPluginOptions},
+    utils::Trackable,
+};
+
+static COMPONENT_NAME: Lazy<Regex> = Lazy::new(|| Regex::new("^[A-Z]").unwrap());
+static HOOK_NAME: Lazy<Regex> = Lazy::new(|| Regex::new("^use[A-Z]").unwrap());
+
+const NEXT_ROUTE_FILES: &[&str] = &[
+    "page",
+    "layout",
+    "template",
+    "loading",
+    "error",
+    "global-error",
+    "not-found",
+    "default",
+];
+
+#[derive(Debug, Clone)]
+enum FileNaming {
+    Raw,
+    Normalized { route_files: Vec<String> },
+}
+
+/// Name anonymous default exports get from their file
+#[derive(Debug, Clone)]
+pub struct FileName {
+    pub name: String,
+    /// framework route files are components regardless of the name
+    pub is_route: bool,
+}
+
+fn capitalize(part: &str) -> String {
+    let mut chars = part.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// `user-card` -> `UserCard`, `use_cart` -> `useCart`
+fn convert_case(stem: &str) -> String {
+    let parts = stem
+        .split(['-', '_'])
+        .filter(|it| !it.is_empty())
+        .collect::<Vec<_>>();
+    let is_hook = parts.first().is_some_and(|first| {
+        first
+            .strip_prefix("use")
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_uppercase))
+    });
+    let name = parts.iter().map(|it| capitalize(it)).collect::<String>();
+    match name.strip_prefix("Use") {
+        Some(rest) if is_hook => format!("use{rest}"),
+        _ => name,
+    }
+}
+
+/// `user-card.test.tsx` -> `user-card`
+fn stem(segment: &str) -> &str {
+    segment.split('.').next().unwrap_or(segment)
+}
+
+/// `/src/user-card/index.tsx?v=1a2b` -> `UserCard`
+fn normalized_file_name(path: &str, route_files: &[String]) -> Option<FileName> {
+    let path = path.split(['?', '#']).next()?;
+    let mut segments = path.rsplit(['/', '\\']).filter(|it| !it.is_empty());
+    let file = stem(segments.next()?);
+    if route_files.iter().any(|it| it == file) {
+        return Some(FileName {
+            name: convert_case(file),
+            is_route: true,
+        });
+    }
+    let name = match file {
+        "index" => stem(segments.next()?),
+        _ => file,
+    };
+    Some(FileName {
+        name: convert_case(name),
+        is_route: false,
+    })
+}
+
+/// `Icon*` matches `Icon` and `IconButton`
+fn name_glob(glob: &str) -> Regex {
+    let pattern = glob
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{pattern}$")).unwrap()
+}
+
+/// Naming conventions of components and hooks, and names tracking is forced for
+#[derive(Debug, Clone)]
+pub struct Naming {
+    component: Regex,
+    hook: Regex,
+    track: Vec<Regex>,
+    ignore: Vec<Regex>,
+    file_naming: FileNaming,
+}
+
+impl Naming {
+    pub fn from_options(options: &PreactSignalsPluginOptions) -> Self {
+        Naming {
+            component: options
+                .component_name_pattern
+                .as_ref()
+                .map_or_else(|| COMPONENT_NAME.clone(), |it| it.0.clone()),
+            hook: options
+                .hook_name_pattern
+                .as_ref()
+                .map_or_else(|| HOOK_NAME.clone(), |it| it.0.clone()),
+            track: options.track_names.iter().map(|it| name_glob(it)).collect(),
+            ignore: options
+                .ignore_names
+                .iter()
+                .map(|it| name_glob(it))
+                .collect(),
+            file_naming: match &options.file_naming {
+                FileNamingOption::Preset(FileNamingPreset::Raw) => FileNaming::Raw,
+                FileNamingOption::Preset(FileNamingPreset::Normalized) => FileNaming::Normalized {
+                    route_files: vec![],
+                },
+                FileNamingOption::Preset(FileNamingPreset::Next) => FileNaming::Normalized {
+                    route_files: NEXT_ROUTE_FILES.iter().map(|it| it.to_string()).collect(),
+                },
+                FileNamingOption::Custom(CustomFileNaming { route_files }) => {
+                    FileNaming::Normalized {
+                        route_files: route_files.clone(),
+                    }
+                }
+            },
+        }
+    }
+
+    /// name of anonymous default exports in the file at `path`
+    pub fn file_name(&self, path: &str) -> Option<FileName> {
+        match &self.file_naming {
+            FileNaming::Raw => {
+                let name = path.rsplit(['/', '\\']).next()?;
+                Some(FileName {
+                    name: name.to_owned(),
+                    is_route: false,
+                })
+            }
+            FileNaming::Normalized { route_files } => normalized_file_name(path, route_files),
+        }
+    }
+
+    pub fn file_trackable(&self, file: &FileName) -> Option<Trackable> {
+        match file.is_route {
+            true => Some(Trackable::Component),
+            false => self.trackable(&file.name),
+        }
+    }
+
+    /// kind of a function with this name, components are checked first
+    pub fn trackable(&self, name: &str) -> Option<Trackable> {
+        if self.component.is_match(name) {
+            Some(Trackable::Component)
+        } else if self.hook.is_match(name) {
+            Some(Trackable::Hook)
+        } else {
+            None
+        }
+    }
+
+    /// `Some(true)` for names in `trackNames`, `Some(false)` for names in `ignoreNames`,
+    /// `ignoreNames` wins when both match
+    pub fn forced(&self, name: &str) -> Option<bool> {
+        if self.ignore.iter().any(|it| it.is_match(name)) {
+            Some(false)
+        } else if self.track.iter().any(|it| it.is_match(name)) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Native pre-pass building the signal manifest, only compiled with the `prepass` feature so
+//! the parser and the resolver don't end up in the plugin.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use swc_core::{
+    common::{sync::Lrc, FileName, Globals, Mark, SourceMap, GLOBALS},
+    ecma::{
+        ast::*,
+        parser::{parse_file_as_program, EsSyntax, Syntax, TsSyntax},
+        transforms::base::resolver,
+        visit::{Visit, VisitWith},
+    },
+};
+
+use crate::{
+    accessors::Accessors,
+    bindings::SignalBindings,
+    call_graph::CallGraph,
+    jsx::JsxFactories,
+    manifest::{export_name, ManifestError, SignalManifest},
+    options::UnknownOrigin,
+    utils::ExprExt,
+};
+
+impl SignalManifest {
+    /// Records exports of the module at `file_path` which read signals, directly, through local
+    /// functions or through functions already in the manifest. `program` must be resolved.
+    ///
+    /// Returns `true` when the manifest has changed.
+    pub fn record(
+        &mut self,
+        program: &Program,
+        file_path: &str,
+        root: Option<&str>,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool {
+        let bindings = SignalBindings::collect(program);
+        let imported = self.imported_readers(program, Some(file_path), root);
+        // only readers are recorded, so JSX pragmas don't matter
+        let mut factories = JsxFactories::default();
+        factories.collect(program);
+        let graph = CallGraph::collect(
+            program,
+            &bindings,
+            accessors,
+            unknown_origin,
+            &factories,
+            imported,
+        );
+        let mut exports = ReaderExports {
+            manifest: self,
+            file_path,
+            root,
+            graph: &graph,
+            bindings: &bindings,
+            accessors,
+            unknown_origin,
+            found: BTreeSet::new(),
+        };
+        program.visit_with(&mut exports);
+        let found = exports.found;
+
+        let key = Self::key(file_path, root);
+        if found.is_empty() {
+            return self.modules.remove(&key).is_some();
+        }
+        self.modules.insert(key, found.clone()) != Some(found)
+    }
+
+    /// Native pre-pass: parses `files` and records them until no more exports are found, so
+    /// the order of files doesn't matter
+    pub fn build(
+        files: &[PathBuf],
+        root: Option<&str>,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> Result<Self, ManifestError> {
+        GLOBALS.set(&Globals::new(), || {
+            let mut files = files.to_vec();
+            files.sort();
+            let programs = files
+                .iter()
+                .map(|path| parse(path).map(|program| (path.to_string_lossy(), program)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut manifest = SignalManifest::default();
+            // each pass adds at least one module, or we are done
+            for _ in 0..=programs.len() {
+                let mut changed = false;
+                for (path, program) in &programs {
+                    changed |= manifest.record(program, path, root, accessors, unknown_origin);
+                }
+                if !changed {
+                    break;
+                }
+            }
+            Ok(manifest)
+        })
+    }
+}
+
+/// parses and resolves a file the same way swc does before running plugins
+fn parse(path: &Path) -> Result<Program, ManifestError> {
+    let source = fs::read_to_string(path).map_err(|it| ManifestError::Io(path.to_owned(), it))?;
+    let extension = path.extension().and_then(|it| it.to_str()).unwrap_or("");
+    let is_ts = matches!(extension, "ts" | "tsx" | "mts" | "cts");
+    let syntax = if is_ts {
+        Syntax::Typescript(TsSyntax {
+            tsx: extension == "tsx",
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            explicit_resource_management: true,
+            ..Default::default()
+        })
+    };
+    let cm = Lrc::new(SourceMap::default());
+    let file = cm.new_source_file(Lrc::new(FileName::Real(path.to_owned())), source);
+    let program = parse_file_as_program(&file, syntax, EsVersion::latest(), None, &mut vec![])
+        .map_err(|it| ManifestError::Parse(path.to_owned(), format!("{:?}", it.kind())))?;
+    Ok(program.apply(resolver(Mark::new(), Mark::new(), is_ts)))
+}
+
+/// Collects exported names of functions reading signals
+struct ReaderExports<'a> {
+    manifest: &'a SignalManifest,
+    file_path: &'a str,
+    root: Option<&'a str>,
+    graph: &'a CallGraph,
+    bindings: &'a SignalBindings,
+    accessors: &'a Accessors,
+    unknown_origin: UnknownOrigin,
+    found: BTreeSet<String>,
+}
+
+impl ReaderExports<'_> {
+    fn add_if_reader(&mut self, name: &str, id: Id) {
+        if self.graph.is_reader(&id) {
+            self.found.insert(name.to_owned());
+        }
+    }
+
+    fn reexports(&self, src: &Str) -> Option<&BTreeSet<String>> {
+        self.manifest
+            .exports(Some(self.file_path), self.root, &src.value)
+    }
+}
+
+impl Visit for ReaderExports<'_> {
+    fn visit_export_decl(&mut self, n: &ExportDecl) {
+        match &n.decl {
+            Decl::Fn(FnDecl { ident, .. }) => self.add_if_reader(&ident.sym, ident.to_id()),
+            Decl::Var(var) => {
+                for declarator in &var.decls {
+                    if let Pat::Ident(ident) = &declarator.name {
+                        self.add_if_reader(&ident.sym, ident.to_id());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_export_default_decl(&mut self, n: &ExportDefaultDecl) {
+        let reads = match &n.decl {
+            DefaultDecl::Fn(FnExpr { function, .. }) => self.graph.reads_signals(
+                &**function,
+                self.bindings,
+                self.accessors,
+                self.unknown_origin,
+            ),
+            _ => false,
+        };
+        if reads {
+            self.found.insert("default".into());
+        }
+    }
+
+    fn visit_export_default_expr(&mut self, n: &ExportDefaultExpr) {
+        let reads = match n.expr.unwrap_ts() {
+            Expr::Ident(ident) => self.graph.is_reader(&ident.to_id()),
+            Expr::Arrow(arrow) => {
+                self.graph
+                    .reads_signals(arrow, self.bindings, self.accessors, self.unknown_origin)
+            }
+            Expr::Fn(FnExpr { function, .. }) => self.graph.reads_signals(
+                &**function,
+                self.bindings,
+                self.accessors,
+                self.unknown_origin,
+            ),
+            _ => false,
+        };
+        if reads {
+            self.found.insert("default".into());
+        }
+    }
+
+    fn visit_named_export(&mut self, n: &NamedExport) {
+        if n.type_only {
+            return;
+        }
+        // `export { useCart } from "./cart"`
+        let reexported = n.src.as_deref().map(|src| self.reexports(src));
+        let mut found = vec![];
+        for specifier in &n.specifiers {
+            let ExportSpecifier::Named(ExportNamedSpecifier {
+                orig,
+                exported,
+                is_type_only: false,
+                ..
+            }) = specifier
+            else {
+                continue;
+            };
+            let name = export_name(exported.as_ref().unwrap_or(orig));
+            let is_reader = match (&reexported, orig) {
+                (Some(exports), orig) => exports.is_some_and(|it| it.contains(export_name(orig))),
+                (None, ModuleExportName::Ident(ident)) => self.graph.is_reader(&ident.to_id()),
+                (None, ModuleExportName::Str(_)) => false,
+            };
+            if is_reader {
+                found.push(name.to_owned());
+            }
+        }
+        self.found.extend(found);
+    }
+
+    fn visit_export_all(&mut self, n: &ExportAll) {
+        if n.type_only {
+            return;
+        }
+        // `export *` doesn't re-export the default export
+        if let Some(exports) = self.reexports(&n.src) {
+            let names = exports
+                .iter()
+                .filter(|it| *it != "default")
+                .cloned()
+                .collect::<Vec<_>>();
+            self.found.extend(names);
+        }
+    }
+}
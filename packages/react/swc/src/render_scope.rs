@@ -0,0 +1,137 @@
+use swc_core::ecma::{
+    ast::*,
+    visit::{Visit, VisitWith},
+};
+
+use crate::{jsx::JsxFactories, utils::ExprExt};
+
+// callbacks passed to these are invoked synchronously, so they're part of the render
+const RENDER_PHASE_METHODS: &[&str] = &[
+    "map",
+    "flatMap",
+    "filter",
+    "forEach",
+    "reduce",
+    "reduceRight",
+    "some",
+    "every",
+    "find",
+    "findIndex",
+    "findLast",
+    "findLastIndex",
+    "sort",
+    "toSorted",
+];
+const RENDER_PHASE_HOOKS: &[&str] = &["useMemo"];
+
+fn is_render_phase_call(n: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &n.callee else {
+        return false;
+    };
+    match callee.unwrap_parens() {
+        Expr::Ident(ident) => RENDER_PHASE_HOOKS.contains(&ident.sym.as_str()),
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            RENDER_PHASE_METHODS.contains(&prop.sym.as_str())
+                || RENDER_PHASE_HOOKS.contains(&prop.sym.as_str())
+        }
+        _ => false,
+    }
+}
+
+fn is_fn(expr: &Expr) -> bool {
+    matches!(expr.unwrap_ts(), Expr::Fn(_) | Expr::Arrow(_))
+}
+
+/// Keeps track of function boundaries while visiting a component or a hook.
+///
+/// Code of the analyzed function, IIFEs and callbacks of `useMemo`/`.map(..)`-like calls run
+/// during render. Any other nested function (effects, event handlers, render props) runs later,
+/// so it's not visited at all.
+#[derive(Debug, Default)]
+pub struct RenderScope {
+    depth: usize,
+    next_fn_immediate: bool,
+}
+
+impl RenderScope {
+    /// `true` until the analyzed function itself is entered
+    pub fn is_root(&self) -> bool {
+        self.depth == 0
+    }
+}
+
+pub trait RenderScoped: Visit + Sized {
+    fn render_scope(&mut self) -> &mut RenderScope;
+
+    fn visit_fn_in_render_scope<N>(&mut self, n: &N)
+    where
+        N: VisitWith<Self>,
+    {
+        let scope = self.render_scope();
+        if !scope.is_root() && !std::mem::take(&mut scope.next_fn_immediate) {
+            return;
+        }
+        scope.next_fn_immediate = false;
+        scope.depth += 1;
+        n.visit_children_with(self);
+        self.render_scope().depth -= 1;
+    }
+
+    fn visit_call_in_render_scope(&mut self, n: &CallExpr) {
+        let is_render_phase = is_render_phase_call(n);
+        if let Callee::Expr(callee) = &n.callee {
+            self.render_scope().next_fn_immediate = is_fn(callee);
+        }
+        n.callee.visit_with(self);
+        for arg in &n.args {
+            self.render_scope().next_fn_immediate = is_render_phase && is_fn(&arg.expr);
+            arg.visit_with(self);
+        }
+        self.render_scope().next_fn_immediate = false;
+        n.type_args.visit_with(self);
+    }
+}
+
+pub struct HasRenderJSX<'a> {
+    factories: &'a JsxFactories,
+    scope: RenderScope,
+    pub found: bool,
+}
+impl<'a> HasRenderJSX<'a> {
+    pub fn new(factories: &'a JsxFactories) -> Self {
+        HasRenderJSX {
+            factories,
+            scope: RenderScope::default(),
+            found: false,
+        }
+    }
+}
+impl RenderScoped for HasRenderJSX<'_> {
+    fn render_scope(&mut self) -> &mut RenderScope {
+        &mut self.scope
+    }
+}
+impl Visit for HasRenderJSX<'_> {
+    fn visit_function(&mut self, n: &Function) {
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.visit_fn_in_render_scope(n);
+    }
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.factories.is_factory_call(n) {
+            self.found = true;
+            return;
+        }
+        self.visit_call_in_render_scope(n);
+    }
+    fn visit_jsx_element(&mut self, _: &JSXElement) {
+        self.found = true;
+    }
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+        self.found = true;
+    }
+}
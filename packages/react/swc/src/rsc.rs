@@ -0,0 +1,68 @@
+use regex::Regex;
+use swc_core::ecma::ast::Str;
+
+use crate::options::{RscConfig, RscOption};
+
+pub const USE_CLIENT_DIRECTIVE: &str = "use client";
+pub const USE_SERVER_DIRECTIVE: &str = "use server";
+
+/// `src/**/*.client.tsx`, `**` matches across directories and `*` within one
+fn path_glob(glob: &str) -> Regex {
+    let mut pattern = String::new();
+    let mut rest = glob;
+    while let Some(char) = rest.chars().next() {
+        let (part, len) = if rest.starts_with("**/") {
+            // `src/**/page.tsx` matches `src/page.tsx` too
+            ("(?:.*/)?".to_owned(), 3)
+        } else if rest.starts_with("**") {
+            (".*".to_owned(), 2)
+        } else if char == '*' {
+            ("[^/]*".to_owned(), 1)
+        } else {
+            (regex::escape(&char.to_string()), char.len_utf8())
+        };
+        pattern.push_str(&part);
+        rest = &rest[len..];
+    }
+    // file paths may be absolute, so the glob can match any trailing part of the path
+    Regex::new(&format!("(?:^|/){pattern}$")).unwrap()
+}
+
+/// React Server Components rules: only client modules are instrumented
+#[derive(Debug, Clone)]
+pub struct Rsc {
+    client_files: Vec<Regex>,
+}
+
+impl Rsc {
+    pub fn from_options(option: &RscOption) -> Option<Self> {
+        match option {
+            RscOption::Enabled(false) => None,
+            RscOption::Enabled(true) => Some(Rsc {
+                client_files: vec![],
+            }),
+            RscOption::Config(RscConfig { client_files }) => Some(Rsc {
+                client_files: client_files.iter().map(|it| path_glob(it)).collect(),
+            }),
+        }
+    }
+
+    /// `"use client"` modules and modules matching client globs, `"use server"` wins over globs
+    pub fn is_client_module<'a>(
+        &self,
+        mut directives: impl Iterator<Item = &'a Str>,
+        file_path: Option<&str>,
+    ) -> bool {
+        match directives.find_map(|it| match it.value.as_str() {
+            USE_CLIENT_DIRECTIVE => Some(true),
+            USE_SERVER_DIRECTIVE => Some(false),
+            _ => None,
+        }) {
+            Some(is_client) => is_client,
+            None => file_path.is_some_and(|path| {
+                let path = path.replace('\\', "/");
+                self.client_files.iter().any(|it| it.is_match(&path))
+            }),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{ast::*, atoms::Atom, utils::private_ident},
+};
+
+use crate::{
+    options::{CustomRuntime, ImportKind, RuntimeOption, RuntimePreset, UsageArg, UsageArgs},
+    utils::{
+        add_import, add_require, find_default_import, find_import, find_namespace_import,
+        find_namespace_require, find_require, Trackable,
+    },
+};
+
+const SAFE_REACT_SOURCE: &str = "@preact-signals/safe-react/tracking";
+const PREACT_SIGNALS_REACT_SOURCE: &str = "@preact/signals-react/runtime";
+
+/// Tracking functions of a runtime and the way they are imported and called
+#[derive(Debug, Clone)]
+pub struct Runtime {
+    source: String,
+    import: ImportKind,
+    use_signals: String,
+    track_class_signals: Option<String>,
+    finish: Atom,
+    usage: Option<UsageArgs>,
+    finishes_stores: bool,
+}
+
+/// both runtimes use the same usage constants: 1 for components, 2 for hooks
+fn managed_usage() -> Option<UsageArgs> {
+    Some(UsageArgs {
+        component: UsageArg::Number(1),
+        hook: UsageArg::Number(2),
+    })
+}
+
+impl Runtime {
+    pub fn from_options(runtime: &RuntimeOption, import_source: Option<&str>) -> Self {
+        let mut res = match runtime {
+            RuntimeOption::Preset(RuntimePreset::SafeReact) => Runtime {
+                source: SAFE_REACT_SOURCE.into(),
+                import: ImportKind::Named,
+                use_signals: "useSignals".into(),
+                track_class_signals: Some("trackClassSignals".into()),
+                finish: "f".into(),
+                usage: managed_usage(),
+                finishes_stores: false,
+            },
+            RuntimeOption::Preset(RuntimePreset::PreactSignalsReact) => Runtime {
+                source: PREACT_SIGNALS_REACT_SOURCE.into(),
+                import: ImportKind::Named,
+                use_signals: "useSignals".into(),
+                track_class_signals: None,
+                finish: "f".into(),
+                usage: managed_usage(),
+                // stores of `useSignals()` without usage are finished on the next render or microtask
+                finishes_stores: true,
+            },
+            RuntimeOption::Custom(CustomRuntime {
+                source,
+                import,
+                use_signals,
+                track_class_signals,
+                finish,
+                usage,
+                finishes_stores,
+            }) => Runtime {
+                source: source.clone(),
+                import: *import,
+                use_signals: use_signals.clone(),
+                track_class_signals: track_class_signals.clone(),
+                finish: finish.as_str().into(),
+                usage: usage.clone(),
+                finishes_stores: *finishes_stores,
+            },
+        };
+        if let Some(import_source) = import_source {
+            res.source = import_source.into();
+        }
+        res
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// finish method of the effect store
+    pub fn finish(&self) -> &Atom {
+        &self.finish
+    }
+
+    /// stores aren't left open when nothing calls `finish`
+    pub fn finishes_stores(&self) -> bool {
+        self.finishes_stores
+    }
+
+    pub fn tracks_classes(&self) -> bool {
+        self.track_class_signals.is_some()
+    }
+
+    /// `useSignals` of both presets can be called in place of this runtime's
+    pub fn calls_like_presets(&self) -> bool {
+        self.import == ImportKind::Named
+            && self.use_signals == "useSignals"
+            && self.finish == "f"
+            && self.usage == managed_usage()
+    }
+
+    /// both functions are called on the same namespace import
+    pub fn is_namespace(&self) -> bool {
+        self.import == ImportKind::Namespace
+    }
+
+    pub fn use_signals_local(&self) -> Ident {
+        match self.import {
+            ImportKind::Namespace => private_ident!("_signalsRuntime"),
+            ImportKind::Named | ImportKind::Default => private_ident!("_useSignals"),
+        }
+    }
+
+    pub fn track_class_signals_local(&self) -> Ident {
+        private_ident!("_trackClassSignals")
+    }
+
+    fn callee(&self, local: Ident, member: &str) -> Expr {
+        match self.import {
+            ImportKind::Namespace => Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(local)),
+                prop: MemberProp::Ident(IdentName::new(member.into(), DUMMY_SP)),
+            }),
+            ImportKind::Named | ImportKind::Default => Expr::Ident(local),
+        }
+    }
+
+    fn usage_arg(&self, trackable: Option<&Trackable>) -> Option<Expr> {
+        let usage = self.usage.as_ref()?;
+        let arg = match trackable? {
+            Trackable::Component => &usage.component,
+            Trackable::Hook => &usage.hook,
+            Trackable::Unknown => return None,
+        };
+        Some(match arg {
+            UsageArg::Number(value) => Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: *value as f64,
+                raw: None,
+            })),
+            UsageArg::String(value) => Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.as_str().into(),
+                raw: None,
+            })),
+        })
+    }
+
+    /// `_useSignals(usage)`
+    pub fn use_signals_call(&self, local: Ident, trackable: Option<&Trackable>) -> Expr {
+        Expr::Call(CallExpr {
+            ctxt: local.ctxt,
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(self.callee(local, &self.use_signals))),
+            args: self
+                .usage_arg(trackable)
+                .map(|expr| ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr),
+                })
+                .into_iter()
+                .collect(),
+            type_args: None,
+        })
+    }
+
+    /// `_trackClassSignals(this)`, `None` when the runtime can't track class components
+    pub fn track_class_signals_call(&self, local: Ident) -> Option<Expr> {
+        let member = self.track_class_signals.as_ref()?;
+        Some(Expr::Call(CallExpr {
+            ctxt: local.ctxt,
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(self.callee(local, member))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+            }],
+            type_args: None,
+        }))
+    }
+
+    /// `_trackClassSignals.m(this)`, where `method` is `m` on mount or `u` on unmount
+    pub fn class_lifecycle_call(&self, local: Ident, method: &str) -> Option<Expr> {
+        let member = self.track_class_signals.as_ref()?;
+        Some(Expr::Call(CallExpr {
+            ctxt: local.ctxt,
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(self.callee(local, member)),
+                prop: MemberProp::Ident(IdentName::new(method.into(), DUMMY_SP)),
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+            }],
+            type_args: None,
+        }))
+    }
+
+    /// local names of existing `useSignals` and `trackClassSignals` imports
+    pub fn find_imports(&self, items: &[ModuleItem]) -> (Option<Ident>, Option<Ident>) {
+        let use_signals = match self.import {
+            ImportKind::Named => find_import(items, &self.source, &self.use_signals),
+            ImportKind::Default => find_default_import(items, &self.source),
+            ImportKind::Namespace => return (find_namespace_import(items, &self.source), None),
+        };
+        let track_class_signals = self
+            .track_class_signals
+            .as_ref()
+            .and_then(|member| find_import(items, &self.source, member));
+        (use_signals, track_class_signals)
+    }
+
+    /// `find_imports` for `require` calls
+    pub fn find_requires(&self, stmts: &[Stmt]) -> (Option<Ident>, Option<Ident>) {
+        let use_signals = match self.import {
+            ImportKind::Named => find_require(stmts, &self.source, &self.use_signals),
+            ImportKind::Default => find_require(stmts, &self.source, "default"),
+            ImportKind::Namespace => return (find_namespace_require(stmts, &self.source), None),
+        };
+        let track_class_signals = self
+            .track_class_signals
+            .as_ref()
+            .and_then(|member| find_require(stmts, &self.source, member));
+        (use_signals, track_class_signals)
+    }
+
+    fn source_str(&self) -> Str {
+        Str {
+            span: DUMMY_SP,
+            value: self.source.as_str().into(),
+            raw: None,
+        }
+    }
+
+    fn member_ident(local: &Ident, member: &str) -> Ident {
+        Ident::new(member.into(), DUMMY_SP, local.ctxt)
+    }
+
+    pub fn use_signals_import(&self, local: Ident) -> ImportDecl {
+        match self.import {
+            ImportKind::Named => {
+                let member = Self::member_ident(&local, &self.use_signals);
+                add_import(local, self.source_str(), Some(member))
+            }
+            ImportKind::Default => add_import(local, self.source_str(), None),
+            ImportKind::Namespace => ImportDecl {
+                span: DUMMY_SP,
+                phase: ImportPhase::Evaluation,
+                specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                    span: DUMMY_SP,
+                    local,
+                })],
+                src: self.source_str().into(),
+                type_only: false,
+                with: None,
+            },
+        }
+    }
+
+    pub fn track_class_signals_import(&self, local: Ident) -> Option<ImportDecl> {
+        let member = Self::member_ident(&local, self.track_class_signals.as_ref()?);
+        Some(add_import(local, self.source_str(), Some(member)))
+    }
+
+    pub fn use_signals_require(&self, local: Ident) -> Stmt {
+        let ctxt = local.ctxt;
+        let member = match self.import {
+            ImportKind::Named => Some(Self::member_ident(&local, &self.use_signals)),
+            ImportKind::Default => Some(Self::member_ident(&local, "default")),
+            ImportKind::Namespace => None,
+        };
+        add_require(local, self.source_str(), member, ctxt)
+    }
+
+    pub fn track_class_signals_require(&self, local: Ident) -> Option<Stmt> {
+        let ctxt = local.ctxt;
+        let member = Self::member_ident(&local, self.track_class_signals.as_ref()?);
+        Some(add_require(local, self.source_str(), Some(member), ctxt))
+    }
+}
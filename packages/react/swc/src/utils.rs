@@ -1,6 +1,17 @@
-use regex::Regex;
+use std::collections::HashSet;
+
+use crate::{
+    accessors::Accessors,
+    bindings::{HasSignalRead, SignalBindings},
+    call_graph::RenderCalls,
+    hocs::Hocs,
+    jsx::JsxFactories,
+    naming::Naming,
+    options::{UnknownOrigin, WrapStyle},
+    render_scope::HasRenderJSX,
+};
 use swc_core::{
-    common::{sync::Lazy, Mark, Span, SyntaxContext, DUMMY_SP},
+    common::{Mark, Span, SyntaxContext, DUMMY_SP},
     ecma::{
         ast::*,
         atoms::Atom,
@@ -33,15 +44,13 @@ impl FunctionLikeExpr for FnDecl {
         self.function.is_regular()
     }
 }
-
-fn is_component_name(name: &str) -> bool {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new("^[A-Z]").unwrap());
-    RE.is_match(name)
-}
-
-fn is_hook_name(name: &str) -> bool {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new("^use[A-Z]").unwrap());
-    RE.is_match(name)
+impl FunctionLikeExpr for FunctionLike<'_> {
+    fn is_regular(&self) -> bool {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => arrow_expr.is_regular(),
+            FunctionLike::Fn(fn_expr) => fn_expr.is_regular(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -52,40 +61,39 @@ pub enum Trackable {
 }
 
 pub trait MaybeComponentName {
-    fn is_trackable(&self) -> Option<Trackable>;
+    /// name used to detect components and hooks
+    fn component_name(&self) -> Option<&str>;
+
+    fn is_trackable(&self, naming: &Naming) -> Option<Trackable> {
+        naming.trackable(self.component_name()?)
+    }
 }
 
 impl MaybeComponentName for str {
-    fn is_trackable(&self) -> Option<Trackable> {
-        if is_component_name(self) {
-            Some(Trackable::Component)
-        } else if is_hook_name(self) {
-            Some(Trackable::Hook)
-        } else {
-            None
-        }
+    fn component_name(&self) -> Option<&str> {
+        Some(self)
     }
 }
 
 impl MaybeComponentName for Str {
-    fn is_trackable(&self) -> Option<Trackable> {
-        self.value.as_str().is_trackable()
+    fn component_name(&self) -> Option<&str> {
+        Some(self.value.as_str())
     }
 }
 impl MaybeComponentName for Ident {
-    fn is_trackable(&self) -> Option<Trackable> {
-        self.sym.as_str().is_trackable()
+    fn component_name(&self) -> Option<&str> {
+        Some(self.sym.as_str())
     }
 }
 impl MaybeComponentName for BindingIdent {
-    fn is_trackable(&self) -> Option<Trackable> {
-        self.id.is_trackable()
+    fn component_name(&self) -> Option<&str> {
+        self.id.component_name()
     }
 }
 impl MaybeComponentName for Pat {
-    fn is_trackable(&self) -> Option<Trackable> {
+    fn component_name(&self) -> Option<&str> {
         if let Pat::Ident(id) = self {
-            id.is_trackable()
+            id.component_name()
         } else {
             None
         }
@@ -106,9 +114,9 @@ impl MaybeComponentName for Pat {
 } */
 
 impl MaybeComponentName for MemberProp {
-    fn is_trackable(&self) -> Option<Trackable> {
+    fn component_name(&self) -> Option<&str> {
         match self {
-            MemberProp::Ident(ident) => ident.sym.is_trackable(),
+            MemberProp::Ident(ident) => Some(ident.sym.as_str()),
             MemberProp::PrivateName(_) => None,
             MemberProp::Computed(ComputedPropName { span: _, expr }) => {
                 let Expr::Lit(Lit::Str(Str {
@@ -123,94 +131,318 @@ impl MaybeComponentName for MemberProp {
                     is_component_name(left_str.value.as_str())
                 }  */
 
-                value.as_str().is_trackable()
+                Some(value.as_str())
             }
         }
     }
 }
 impl MaybeComponentName for Expr {
-    fn is_trackable(&self) -> Option<Trackable> {
+    fn component_name(&self) -> Option<&str> {
         match self.unwrap_parens() {
-            Expr::Ident(ident) => ident.is_trackable(),
-            Expr::Member(member_expr) => member_expr.prop.is_trackable(),
-            Expr::Lit(Lit::Str(str)) => str.is_trackable(),
+            Expr::Ident(ident) => ident.component_name(),
+            Expr::Member(member_expr) => member_expr.prop.component_name(),
+            Expr::Lit(Lit::Str(str)) => str.component_name(),
             _ => None,
         }
     }
 }
 impl MaybeComponentName for PropName {
-    fn is_trackable(&self) -> Option<Trackable> {
+    fn component_name(&self) -> Option<&str> {
         match self {
-            PropName::Computed(computed_expr) => computed_expr.expr.is_trackable(),
-            PropName::Str(str) => str.is_trackable(),
-            PropName::Ident(ident) => ident.sym.is_trackable(),
+            PropName::Computed(computed_expr) => computed_expr.expr.component_name(),
+            PropName::Str(str) => str.component_name(),
+            PropName::Ident(ident) => Some(ident.sym.as_str()),
             _ => None,
         }
     }
 }
 
+impl MaybeComponentName for PrivateName {
+    fn component_name(&self) -> Option<&str> {
+        Some(self.name.as_str())
+    }
+}
+
 impl MaybeComponentName for AssignTarget {
-    fn is_trackable(&self) -> Option<Trackable> {
+    fn component_name(&self) -> Option<&str> {
         let AssignTarget::Simple(pat) = self else {
             return None;
         };
         match pat {
-            SimpleAssignTarget::Ident(ident) => ident.is_trackable(),
-            SimpleAssignTarget::Member(member) => member.prop.is_trackable(),
-            SimpleAssignTarget::Paren(paren) => paren.expr.is_trackable(),
+            SimpleAssignTarget::Ident(ident) => ident.component_name(),
+            SimpleAssignTarget::Member(member) => member.prop.component_name(),
+            SimpleAssignTarget::Paren(paren) => paren.expr.component_name(),
+            _ => None,
+        }
+    }
+}
+
+fn is_member_of(expr: &MemberExpr, obj: &str, prop: &str) -> bool {
+    matches!(
+        (&*expr.obj, &expr.prop),
+        (Expr::Ident(obj_ident), MemberProp::Ident(prop_ident))
+            if obj_ident.sym == obj && prop_ident.sym == prop
+    )
+}
+
+/// `module.exports = ...`, `exports.default = ...` or `module.exports.default = ...`
+pub fn is_cjs_default_export(target: &AssignTarget) -> bool {
+    let AssignTarget::Simple(SimpleAssignTarget::Member(member)) = target else {
+        return false;
+    };
+    if is_member_of(member, "module", "exports") || is_member_of(member, "exports", "default") {
+        return true;
+    }
+    matches!(
+        (&*member.obj, &member.prop),
+        (Expr::Member(obj), MemberProp::Ident(prop))
+            if prop.sym == "default" && is_member_of(obj, "module", "exports")
+    )
+}
+
+const COMPONENT_SUPER_CLASSES: &[&str] = &["Component", "PureComponent"];
+
+/// `Component`, `PureComponent`, `React.Component`, etc.
+pub fn is_component_super_class(expr: &Expr) -> bool {
+    match expr.unwrap_ts() {
+        Expr::Ident(ident) => COMPONENT_SUPER_CLASSES.contains(&ident.sym.as_str()),
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => COMPONENT_SUPER_CLASSES.contains(&prop.sym.as_str()),
+        _ => false,
+    }
+}
+
+/// Classes extending `Component` are components regardless of their name,
+/// other classes need a component name
+pub struct ClassComponentName<'a> {
+    pub ident: Option<&'a Ident>,
+    pub extends_component: bool,
+}
+
+impl MaybeComponentName for ClassComponentName<'_> {
+    fn component_name(&self) -> Option<&str> {
+        self.ident?.component_name()
+    }
+
+    fn is_trackable(&self, naming: &Naming) -> Option<Trackable> {
+        if self.extends_component {
+            return Some(Trackable::Component);
+        }
+        match self.ident.and_then(|it| it.is_trackable(naming)) {
+            Some(Trackable::Component) => Some(Trackable::Component),
             _ => None,
         }
     }
 }
 
+pub fn find_render_method(class: &mut Class) -> Option<&mut ClassMethod> {
+    class.body.iter_mut().find_map(|member| match member {
+        ClassMember::Method(
+            method @ ClassMethod {
+                kind: MethodKind::Method,
+                is_static: false,
+                ..
+            },
+        ) if matches!(&method.key, PropName::Ident(key) if key.sym == "render")
+            && method.function.is_regular() =>
+        {
+            Some(method)
+        }
+        _ => None,
+    })
+}
+
 pub enum FunctionLike<'a> {
     Arrow(&'a mut ArrowExpr),
     Fn(&'a mut FnExpr),
 }
+/// `hook_call` creates the effect store, `finish` is the method finishing it
 pub fn wrap_with_use_signals(
-    n: &Vec<Stmt>,
-    use_signals_ident: Ident,
-    trackable: Option<Trackable>,
+    n: &[Stmt],
+    hook_call: Expr,
+    style: WrapStyle,
+    finish: &Atom,
     // unresolved_mark: Mark,
 ) -> Vec<Stmt> {
+    match style {
+        WrapStyle::TryFinally => wrap_with_effect_store(n, hook_call, finish),
+        WrapStyle::Using => wrap_with_using(n, hook_call),
+        WrapStyle::Prepend => {
+            let (prologue, body) = split_prologue(n);
+            let mut res = Vec::with_capacity(n.len() + 1);
+            res.extend_from_slice(prologue);
+            res.push(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(hook_call),
+            }));
+
+            res.extend_from_slice(body);
+
+            res
+        }
+    }
+}
+
+/// `EffectStore` implements `Symbol.dispose`, so `using` finishes it the same way as `finally`
+fn wrap_with_using(n: &[Stmt], effect_store: Expr) -> Vec<Stmt> {
     let signal_effect_ident = private_ident!("_effect");
-    let (wrap_in_try_finally, hook_arg) = match trackable {
-        Some(Trackable::Component) => (true, Some(1_f64)),
-        Some(Trackable::Hook) => (true, Some(2_f64)),
-        Some(Trackable::Unknown) => (false, None),
-        None => (true, None),
-    };
+    let (prologue, body) = split_prologue(n);
 
-    let hook_call = Expr::Call(CallExpr {
-        ctxt: use_signals_ident.ctxt,
+    let mut res = Vec::with_capacity(n.len() + 1);
+    res.extend_from_slice(prologue);
+    res.push(Stmt::Decl(Decl::Using(Box::new(UsingDecl {
         span: DUMMY_SP,
-        callee: Callee::Expr(Box::new(Expr::Ident(use_signals_ident))),
-        args: match hook_arg {
-            Some(value) => vec![ExprOrSpread {
-                spread: None,
-                expr: Box::new(Expr::Lit(Lit::Num(Number {
-                    span: DUMMY_SP,
-                    value: value,
-                    raw: None,
-                }))),
-            }],
-            None => vec![],
-        },
-        type_args: None,
-    });
-    if !wrap_in_try_finally {
-        let mut res = Vec::with_capacity(n.capacity() + 1);
-        res.push(Stmt::Expr(ExprStmt {
+        is_await: false,
+        decls: vec![VarDeclarator {
+            definite: false,
             span: DUMMY_SP,
-            expr: Box::new(hook_call),
-        }));
+            init: Some(Box::new(effect_store)),
+            name: Pat::Ident(BindingIdent {
+                id: signal_effect_ident,
+                type_ann: None,
+            }),
+        }],
+    }))));
+    res.extend_from_slice(body);
 
-        n.iter().for_each(|it| res.push(it.clone()));
+    res
+}
 
-        return res;
+/// `render` of class components can't use hooks, so `trackClassSignals(this)` is used instead.
+/// Nothing finishes its store when it's prepended, so `Prepend` is wrapped with try/finally
+pub fn wrap_with_class_signals(
+    n: &[Stmt],
+    track_call: Expr,
+    style: WrapStyle,
+    finish: &Atom,
+) -> Vec<Stmt> {
+    match style {
+        WrapStyle::Using => wrap_with_using(n, track_call),
+        WrapStyle::TryFinally | WrapStyle::Prepend => wrap_with_effect_store(n, track_call, finish),
     }
+}
+
+fn expr_stmt(expr: Expr) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(expr),
+    })
+}
+
+/// Calls `call` at the start of lifecycle method `name` of a class component, adding the method
+/// (calling the inherited one) when the class doesn't define it.
+///
+/// Returns `false` when `name` is defined in a way the call can't be added to
+pub fn add_lifecycle_call(class: &mut Class, name: &str, call: Expr) -> bool {
+    let is_name = |key: &PropName| matches!(key, PropName::Ident(key) if key.sym == name);
+    let mut defined = false;
+    for member in &mut class.body {
+        match member {
+            ClassMember::Method(method) if !method.is_static && is_name(&method.key) => {
+                defined = true;
+                if method.kind == MethodKind::Method
+                    && let Some(body) = &mut method.function.body
+                {
+                    body.stmts.insert(0, expr_stmt(call));
+                    return true;
+                }
+            }
+            // instance fields shadow methods of the prototype
+            ClassMember::ClassProp(prop) if !prop.is_static && is_name(&prop.key) => {
+                match prop.value.as_deref_mut().map(|it| it.unwrap_ts_mut()) {
+                    Some(Expr::Arrow(arrow)) => {
+                        match &mut *arrow.body {
+                            BlockStmtOrExpr::BlockStmt(block) => {
+                                block.stmts.insert(0, expr_stmt(call));
+                            }
+                            BlockStmtOrExpr::Expr(expr) => {
+                                let stmts = vec![
+                                    expr_stmt(call),
+                                    Stmt::Return(ReturnStmt {
+                                        span: DUMMY_SP,
+                                        arg: Some(expr.clone()),
+                                    }),
+                                ];
+                                *arrow.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                                    stmts,
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        return true;
+                    }
+                    Some(Expr::Fn(FnExpr {
+                        function:
+                            box Function {
+                                body: Some(body), ..
+                            },
+                        ..
+                    })) => {
+                        body.stmts.insert(0, expr_stmt(call));
+                        return true;
+                    }
+                    _ => return false,
+                }
+            }
+            _ => {}
+        }
+    }
+    if defined {
+        return false;
+    }
+
+    let mut stmts = vec![expr_stmt(call)];
+    // `super.componentDidMount?.()`
+    if class.super_class.is_some() {
+        stmts.push(expr_stmt(Expr::OptChain(OptChainExpr {
+            span: DUMMY_SP,
+            optional: true,
+            base: Box::new(OptChainBase::Call(OptCall {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                callee: Box::new(Expr::SuperProp(SuperPropExpr {
+                    span: DUMMY_SP,
+                    obj: Super { span: DUMMY_SP },
+                    prop: SuperProp::Ident(IdentName::new(name.into(), DUMMY_SP)),
+                })),
+                args: vec![],
+                type_args: None,
+            })),
+        })));
+    }
+    class.body.push(ClassMember::Method(ClassMethod {
+        span: DUMMY_SP,
+        key: PropName::Ident(IdentName::new(name.into(), DUMMY_SP)),
+        function: Box::new(Function {
+            body: Some(BlockStmt {
+                stmts,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        kind: MethodKind::Method,
+        is_static: false,
+        accessibility: None,
+        is_abstract: false,
+        is_optional: false,
+        is_override: false,
+    }));
+    true
+}
+
+/// directives lose their meaning inside of `try`, so they are kept at the top of the body
+fn split_prologue(n: &[Stmt]) -> (&[Stmt], &[Stmt]) {
+    n.split_at(directives(n).count())
+}
 
-    return vec![
+fn wrap_with_effect_store(n: &[Stmt], effect_store: Expr, finish: &Atom) -> Vec<Stmt> {
+    let signal_effect_ident = private_ident!("_effect");
+    let (prologue, n) = split_prologue(n);
+
+    let mut res = prologue.to_vec();
+    res.extend([
         Stmt::Decl(Decl::Var(Box::new(VarDecl {
             ctxt: signal_effect_ident.ctxt,
             span: DUMMY_SP,
@@ -219,7 +451,7 @@ pub fn wrap_with_use_signals(
             decls: vec![VarDeclarator {
                 definite: false,
                 span: DUMMY_SP,
-                init: Some(Box::new(hook_call)),
+                init: Some(Box::new(effect_store)),
                 name: Pat::Ident(BindingIdent {
                     id: signal_effect_ident.clone(),
                     type_ann: None,
@@ -246,18 +478,21 @@ pub fn wrap_with_use_signals(
                         type_args: None,
                         callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
                             span: DUMMY_SP,
-                            prop: MemberProp::Ident(IdentName::new("f".into(), DUMMY_SP)),
+                            prop: MemberProp::Ident(IdentName::new(finish.clone(), DUMMY_SP)),
                             obj: Box::new(Expr::Ident(signal_effect_ident)),
                         }))),
                     })),
                 })],
             }),
         })),
-    ];
+    ]);
+    res
 }
 
 pub trait SignalWrappable {
-    fn wrap_with_use_signals(&mut self, import_use_signals: Ident, arg: Option<Trackable>);
+    fn wrap_with_use_signals(&mut self, hook_call: Expr, style: WrapStyle, finish: &Atom);
+    /// statements of the body, `None` for arrow functions returning an expression
+    fn body_stmts_mut(&mut self) -> Option<&mut Vec<Stmt>>;
 }
 
 impl<'a> FunctionLike<'a> {
@@ -273,52 +508,90 @@ impl<'a> FunctionLike<'a> {
 }
 
 impl SignalWrappable for Function {
-    fn wrap_with_use_signals(&mut self, import_use_signals: Ident, arg: Option<Trackable>) {
+    fn wrap_with_use_signals(&mut self, hook_call: Expr, style: WrapStyle, finish: &Atom) {
         if let Some(body) = &mut self.body {
-            body.stmts = wrap_with_use_signals(&body.stmts, import_use_signals, arg);
+            body.stmts = wrap_with_use_signals(&body.stmts, hook_call, style, finish);
         }
     }
+    fn body_stmts_mut(&mut self) -> Option<&mut Vec<Stmt>> {
+        self.body.as_mut().map(|it| &mut it.stmts)
+    }
 }
 impl<'a> SignalWrappable for FunctionLike<'a> {
-    fn wrap_with_use_signals(&mut self, import_use_signals: Ident, arg: Option<Trackable>) {
+    fn wrap_with_use_signals(&mut self, hook_call: Expr, style: WrapStyle, finish: &Atom) {
         match self {
             FunctionLike::Arrow(arrow_expr) => {
                 let this = &mut *arrow_expr;
                 let mut block = this.body.to_block();
-                let wrapped_body = wrap_with_use_signals(&block.stmts, import_use_signals, arg);
+                let wrapped_body = wrap_with_use_signals(&block.stmts, hook_call, style, finish);
                 block.stmts = wrapped_body;
                 this.body = Box::new(BlockStmtOrExpr::BlockStmt(block.to_owned()));
             }
             FunctionLike::Fn(fn_expr) => {
                 fn_expr
                     .function
-                    .wrap_with_use_signals(import_use_signals, arg);
+                    .wrap_with_use_signals(hook_call, style, finish);
             }
         }
     }
+    fn body_stmts_mut(&mut self) -> Option<&mut Vec<Stmt>> {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => match &mut *arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(block) => Some(&mut block.stmts),
+                BlockStmtOrExpr::Expr(_) => None,
+            },
+            FunctionLike::Fn(fn_expr) => fn_expr.function.body_stmts_mut(),
+        }
+    }
 }
 
-pub fn extract_fn_from_expr<'a>(expr: &'a mut Expr) -> Option<FunctionLike<'a>> {
-    match expr {
-        Expr::Fn(fn_expr) if fn_expr.is_regular() => Some(FunctionLike::Fn(fn_expr)),
-        Expr::Arrow(arrow_expr) if arrow_expr.is_regular() => Some(FunctionLike::Arrow(arrow_expr)),
-        Expr::Call(CallExpr {
-            args,
-            span: _,
-            type_args: _,
-            callee: _,
-            ctxt: _,
-        }) => {
-            if let Some(ExprOrSpread {
-                spread: None,
-                expr: first_arg_expr,
-            }) = args.as_mut_slice().first_mut()
-            {
-                extract_fn_from_expr(first_arg_expr.unwrap_parens_mut())
-            } else {
-                None
-            }
-        }
+pub trait ExprExt {
+    /// `unwrap_parens` which also looks through TypeScript-only wrappers (`as`, `satisfies`, `!`, etc.)
+    fn unwrap_ts(&self) -> &Expr;
+    fn unwrap_ts_mut(&mut self) -> &mut Expr;
+}
+
+impl ExprExt for Expr {
+    fn unwrap_ts(&self) -> &Expr {
+        self.unwrap_with(|e| match e {
+            Expr::Paren(ParenExpr { expr, .. })
+            | Expr::TsAs(TsAsExpr { expr, .. })
+            | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+            | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+            | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+            | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+            | Expr::TsInstantiation(TsInstantiation { expr, .. }) => Some(expr),
+            _ => None,
+        })
+    }
+    fn unwrap_ts_mut(&mut self) -> &mut Expr {
+        self.unwrap_mut_with(|e| match e {
+            Expr::Paren(ParenExpr { expr, .. })
+            | Expr::TsAs(TsAsExpr { expr, .. })
+            | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+            | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+            | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+            | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+            | Expr::TsInstantiation(TsInstantiation { expr, .. }) => Some(expr),
+            _ => None,
+        })
+    }
+}
+
+pub fn extract_fn_from_expr<'a>(expr: &'a mut Expr, hocs: &Hocs) -> Option<FunctionLike<'a>> {
+    match expr.unwrap_ts_mut() {
+        Expr::Fn(fn_expr) => Some(FunctionLike::Fn(fn_expr)),
+        Expr::Arrow(arrow_expr) => Some(FunctionLike::Arrow(arrow_expr)),
+        Expr::Call(call) => extract_fn_from_expr(hocs.component_arg(call)?, hocs),
+        _ => None,
+    }
+}
+
+/// `extract_fn_from_expr` for class components: `memo(class extends Component { .. })`
+pub fn extract_class_from_expr<'a>(expr: &'a mut Expr, hocs: &Hocs) -> Option<&'a mut ClassExpr> {
+    match expr.unwrap_ts_mut() {
+        Expr::Class(class_expr) => Some(class_expr),
+        Expr::Call(call) => extract_class_from_expr(hocs.component_arg(call)?, hocs),
         _ => None,
     }
 }
@@ -341,10 +614,18 @@ impl Blockable for BlockStmtOrExpr {
         }
     }
 }
-struct HasJSX {
+struct HasJSX<'a> {
+    factories: &'a JsxFactories,
     found: bool,
 }
-impl Visit for HasJSX {
+impl Visit for HasJSX<'_> {
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.factories.is_factory_call(n) {
+            self.found = true;
+            return;
+        }
+        n.visit_children_with(self);
+    }
     fn visit_jsx_element(&mut self, _: &JSXElement) {
         self.found = true;
     }
@@ -353,31 +634,28 @@ impl Visit for HasJSX {
     }
 }
 
-struct HasDotValue {
+struct HasDotValue<'a> {
+    accessors: &'a Accessors,
     found: bool,
 }
-impl Visit for HasDotValue {
+impl Visit for HasDotValue<'_> {
     fn visit_member_expr(&mut self, n: &MemberExpr) {
         if self.found {
             return;
         }
 
-        if match &n.prop {
-            MemberProp::Ident(ident) => ident.sym.as_str() == "value",
-            MemberProp::Computed(ComputedPropName { span: _, expr }) => {
-                if let Expr::Lit(Lit::Str(Str {
-                    span: _,
-                    value,
-                    raw: _,
-                })) = expr.unwrap_parens()
-                {
-                    value.as_str() == "value"
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        } {
+        if self.accessors.property_read(n).is_some() {
+            self.found = true;
+            return;
+        }
+        n.visit_children_with(self);
+    }
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.found || self.accessors.is_untracked_call(n) {
+            return;
+        }
+
+        if self.accessors.method_read(n).is_some() {
             self.found = true;
             return;
         }
@@ -385,67 +663,425 @@ impl Visit for HasDotValue {
     }
 }
 
-fn has_jsx<N>(n: &N) -> bool
+fn has_jsx<N>(n: &N, factories: &JsxFactories) -> bool
 where
-    N: VisitWith<HasJSX>,
+    N: for<'a> VisitWith<HasJSX<'a>>,
 {
-    let mut v = HasJSX { found: false };
+    let mut v = HasJSX {
+        factories,
+        found: false,
+    };
     n.visit_children_with(&mut v);
     v.found
 }
-fn has_dot_value<N>(n: &N) -> bool
+fn has_dot_value<N>(n: &N, accessors: &Accessors) -> bool
 where
-    N: VisitWith<HasDotValue>,
+    N: for<'a> VisitWith<HasDotValue<'a>>,
 {
-    let mut v = HasDotValue { found: false };
+    let mut v = HasDotValue {
+        accessors,
+        found: false,
+    };
     n.visit_children_with(&mut v);
     v.found
 }
+fn has_render_jsx<N>(n: &N, factories: &JsxFactories) -> bool
+where
+    N: for<'a> VisitWith<HasRenderJSX<'a>>,
+{
+    let mut v = HasRenderJSX::new(factories);
+    // visiting the node itself, so function boundaries are tracked from the start
+    n.visit_with(&mut v);
+    v.found
+}
+fn has_signal_read<N>(
+    n: &N,
+    bindings: &SignalBindings,
+    accessors: &Accessors,
+    unknown_origin: UnknownOrigin,
+) -> bool
+where
+    N: for<'a> VisitWith<HasSignalRead<'a>>,
+{
+    let mut v = HasSignalRead::new(bindings, accessors, unknown_origin);
+    // visiting the node itself, so its own parameters are collected
+    n.visit_with(&mut v);
+    v.found
+}
+fn render_calls<N>(n: &N) -> HashSet<Id>
+where
+    N: VisitWith<RenderCalls>,
+{
+    let mut v = RenderCalls::default();
+    // visiting the node itself, so function boundaries are tracked from the start
+    n.visit_with(&mut v);
+    v.calls
+}
 
 pub trait Detectable {
-    fn has_jsx(&self) -> bool;
-    fn has_dot_value(&self) -> bool;
+    /// JSX or calls of element factories
+    fn has_jsx(&self, factories: &JsxFactories) -> bool;
+    fn has_dot_value(&self, accessors: &Accessors) -> bool;
+    /// render phase only version of `has_jsx`, used by `auto` mode
+    fn has_render_jsx(&self, factories: &JsxFactories) -> bool;
+    /// scope-aware, render phase only version of `has_dot_value`, used by `auto` mode
+    fn has_signal_read(
+        &self,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool;
+    /// local functions called during render, used by `auto` mode
+    fn render_calls(&self) -> HashSet<Id>;
+    /// statements of the body, `None` for arrow functions returning an expression
+    fn body_stmts(&self) -> Option<&[Stmt]>;
+    /// span of `"use client"`-like string directive at the start of the body
+    fn find_directive(&self, directive: &str) -> Option<Span> {
+        directives(self.body_stmts()?)
+            .find(|it| it.value == directive)
+            .map(|it| it.span)
+    }
+}
+
+fn directive_str(stmt: &Stmt) -> Option<&Str> {
+    match stmt {
+        Stmt::Expr(ExprStmt {
+            expr: box Expr::Lit(Lit::Str(str)),
+            ..
+        }) => Some(str),
+        _ => None,
+    }
+}
+
+/// Directive prologue of a function body or a script
+pub fn directives(stmts: &[Stmt]) -> impl Iterator<Item = &Str> {
+    stmts.iter().map_while(directive_str)
+}
+
+/// Directive prologue of a module
+pub fn module_directives(items: &[ModuleItem]) -> impl Iterator<Item = &Str> {
+    items
+        .iter()
+        .map_while(|it| it.as_stmt().and_then(directive_str))
+}
+
+fn function_body_stmts(n: &Function) -> Option<&[Stmt]> {
+    n.body.as_ref().map(|it| it.stmts.as_slice())
 }
 
 impl Detectable for FunctionLike<'_> {
-    fn has_jsx(&self) -> bool {
+    fn has_jsx(&self, factories: &JsxFactories) -> bool {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => has_jsx(*arrow_expr, factories),
+            FunctionLike::Fn(fn_expr) => has_jsx(*fn_expr, factories),
+        }
+    }
+    fn has_render_jsx(&self, factories: &JsxFactories) -> bool {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => has_render_jsx(*arrow_expr, factories),
+            FunctionLike::Fn(fn_expr) => has_render_jsx(*fn_expr, factories),
+        }
+    }
+    fn has_dot_value(&self, accessors: &Accessors) -> bool {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => has_dot_value(*arrow_expr, accessors),
+            FunctionLike::Fn(fn_expr) => has_dot_value(*fn_expr, accessors),
+        }
+    }
+    fn has_signal_read(
+        &self,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool {
+        match self {
+            FunctionLike::Arrow(arrow_expr) => {
+                has_signal_read(*arrow_expr, bindings, accessors, unknown_origin)
+            }
+            FunctionLike::Fn(fn_expr) => {
+                has_signal_read(*fn_expr, bindings, accessors, unknown_origin)
+            }
+        }
+    }
+    fn render_calls(&self) -> HashSet<Id> {
         match self {
-            FunctionLike::Arrow(arrow_expr) => has_jsx(*arrow_expr),
-            FunctionLike::Fn(fn_expr) => has_jsx(*fn_expr),
+            FunctionLike::Arrow(arrow_expr) => render_calls(*arrow_expr),
+            FunctionLike::Fn(fn_expr) => render_calls(*fn_expr),
         }
     }
-    fn has_dot_value(&self) -> bool {
+    fn body_stmts(&self) -> Option<&[Stmt]> {
         match self {
-            FunctionLike::Arrow(arrow_expr) => has_dot_value(*arrow_expr),
-            FunctionLike::Fn(fn_expr) => has_dot_value(*fn_expr),
+            FunctionLike::Arrow(arrow_expr) => match &*arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(block) => Some(&block.stmts),
+                BlockStmtOrExpr::Expr(_) => None,
+            },
+            FunctionLike::Fn(fn_expr) => function_body_stmts(&fn_expr.function),
         }
     }
 }
 impl Detectable for FnDecl {
-    fn has_jsx(&self) -> bool {
-        has_jsx(&self.function)
+    fn has_jsx(&self, factories: &JsxFactories) -> bool {
+        has_jsx(&self.function, factories)
+    }
+    fn has_render_jsx(&self, factories: &JsxFactories) -> bool {
+        has_render_jsx(&self.function, factories)
+    }
+    fn has_dot_value(&self, accessors: &Accessors) -> bool {
+        has_dot_value(&self.function, accessors)
     }
-    fn has_dot_value(&self) -> bool {
-        has_dot_value(&self.function)
+    fn has_signal_read(
+        &self,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool {
+        has_signal_read(&self.function, bindings, accessors, unknown_origin)
+    }
+    fn render_calls(&self) -> HashSet<Id> {
+        render_calls(&self.function)
+    }
+    fn body_stmts(&self) -> Option<&[Stmt]> {
+        function_body_stmts(&self.function)
     }
 }
 impl Detectable for FnExpr {
-    fn has_jsx(&self) -> bool {
-        has_jsx(&self.function)
+    fn has_jsx(&self, factories: &JsxFactories) -> bool {
+        has_jsx(&self.function, factories)
+    }
+    fn has_render_jsx(&self, factories: &JsxFactories) -> bool {
+        has_render_jsx(&self.function, factories)
     }
-    fn has_dot_value(&self) -> bool {
-        has_dot_value(&self.function)
+    fn has_dot_value(&self, accessors: &Accessors) -> bool {
+        has_dot_value(&self.function, accessors)
+    }
+    fn has_signal_read(
+        &self,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool {
+        has_signal_read(&self.function, bindings, accessors, unknown_origin)
+    }
+    fn render_calls(&self) -> HashSet<Id> {
+        render_calls(&self.function)
+    }
+    fn body_stmts(&self) -> Option<&[Stmt]> {
+        function_body_stmts(&self.function)
     }
 }
 impl Detectable for Function {
-    fn has_jsx(&self) -> bool {
-        has_jsx(self)
+    fn has_jsx(&self, factories: &JsxFactories) -> bool {
+        has_jsx(self, factories)
+    }
+    fn has_render_jsx(&self, factories: &JsxFactories) -> bool {
+        has_render_jsx(self, factories)
+    }
+    fn has_dot_value(&self, accessors: &Accessors) -> bool {
+        has_dot_value(self, accessors)
+    }
+    fn has_signal_read(
+        &self,
+        bindings: &SignalBindings,
+        accessors: &Accessors,
+        unknown_origin: UnknownOrigin,
+    ) -> bool {
+        has_signal_read(self, bindings, accessors, unknown_origin)
+    }
+    fn render_calls(&self) -> HashSet<Id> {
+        render_calls(self)
     }
-    fn has_dot_value(&self) -> bool {
-        has_dot_value(self)
+    fn body_stmts(&self) -> Option<&[Stmt]> {
+        function_body_stmts(self)
+    }
+}
+
+/// `local()` or `local.member()` for namespace imports
+fn is_call_of(expr: &Expr, ids: &[Id]) -> bool {
+    matches!(
+        expr.unwrap_parens(),
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(box Expr::Ident(callee) | box Expr::Member(MemberExpr {
+                obj: box Expr::Ident(callee),
+                ..
+            })),
+            ..
+        }) if ids.contains(&callee.to_id())
+    )
+}
+
+/// `true` for bodies already wrapped by this plugin (or babel transform), where `tracking` are
+/// local names of `useSignals`/`trackClassSignals` and `finish` is the finish method:
+/// `var _effect = _useSignals(); try { ... } finally { _effect.f(); }`,
+/// `using _effect = _useSignals(); ...` or `_useSignals(); ...`
+pub fn is_instrumented(stmts: &[Stmt], tracking: &[Id], finish: &Atom) -> bool {
+    let stmts = &stmts[directives(stmts).count()..];
+    match stmts {
+        [Stmt::Expr(ExprStmt { expr, .. }), ..] => is_call_of(expr, tracking),
+        [Stmt::Decl(Decl::Using(using_decl)), ..] => matches!(
+            using_decl.decls.as_slice(),
+            [VarDeclarator { init: Some(init), .. }] if is_call_of(init, tracking)
+        ),
+        [Stmt::Decl(Decl::Var(var_decl)), Stmt::Try(try_stmt)] => {
+            let [VarDeclarator {
+                name: Pat::Ident(effect),
+                init: Some(init),
+                ..
+            }] = var_decl.decls.as_slice()
+            else {
+                return false;
+            };
+            is_call_of(init, tracking)
+                && try_stmt.finalizer.as_ref().is_some_and(|finalizer| {
+                    finalizer.stmts.iter().any(|it| {
+                        matches!(
+                            it,
+                            Stmt::Expr(ExprStmt {
+                                expr: box Expr::Call(CallExpr {
+                                    callee: Callee::Expr(box Expr::Member(MemberExpr {
+                                        obj: box Expr::Ident(obj),
+                                        prop: MemberProp::Ident(prop),
+                                        ..
+                                    })),
+                                    ..
+                                }),
+                                ..
+                            }) if obj.to_id() == effect.to_id() && prop.sym == *finish
+                        )
+                    })
+                })
+        }
+        _ => false,
     }
 }
 
+/// local name of `member` imported from `source`: `import { member as local } from "source"`
+pub fn find_import(items: &[ModuleItem], source: &str, member: &str) -> Option<Ident> {
+    items.iter().find_map(|it| match it {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && import.src.value == source =>
+        {
+            import
+                .specifiers
+                .iter()
+                .find_map(|specifier| match specifier {
+                    ImportSpecifier::Named(ImportNamedSpecifier {
+                        local,
+                        imported,
+                        is_type_only: false,
+                        ..
+                    }) if match imported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym == member,
+                        Some(ModuleExportName::Str(str)) => str.value == member,
+                        None => local.sym == member,
+                    } =>
+                    {
+                        Some(local.clone())
+                    }
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// local name of the default import: `import local from "source"`
+pub fn find_default_import(items: &[ModuleItem], source: &str) -> Option<Ident> {
+    items.iter().find_map(|it| match it {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && import.src.value == source =>
+        {
+            import
+                .specifiers
+                .iter()
+                .find_map(|specifier| match specifier {
+                    ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                        Some(local.clone())
+                    }
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// local name of the namespace import: `import * as local from "source"`
+pub fn find_namespace_import(items: &[ModuleItem], source: &str) -> Option<Ident> {
+    items.iter().find_map(|it| match it {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && import.src.value == source =>
+        {
+            import
+                .specifiers
+                .iter()
+                .find_map(|specifier| match specifier {
+                    ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                        Some(local.clone())
+                    }
+                    _ => None,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// local name of the whole required module: `var local = require("source")`
+pub fn find_namespace_require(stmts: &[Stmt], source: &str) -> Option<Ident> {
+    stmts.iter().find_map(|it| {
+        let Stmt::Decl(Decl::Var(var_decl)) = it else {
+            return None;
+        };
+        var_decl
+            .decls
+            .iter()
+            .find_map(|declarator| match declarator {
+                VarDeclarator {
+                    name: Pat::Ident(local),
+                    init: Some(box Expr::Call(call)),
+                    ..
+                } if is_require_of(call, source) => Some(local.id.clone()),
+                _ => None,
+            })
+    })
+}
+
+/// local name of `member` required from `source`: `var local = require("source").member`
+pub fn find_require(stmts: &[Stmt], source: &str, member: &str) -> Option<Ident> {
+    stmts.iter().find_map(|it| {
+        let Stmt::Decl(Decl::Var(var_decl)) = it else {
+            return None;
+        };
+        var_decl
+            .decls
+            .iter()
+            .find_map(|declarator| match declarator {
+                VarDeclarator {
+                    name: Pat::Ident(local),
+                    init:
+                        Some(box Expr::Member(MemberExpr {
+                            obj: box Expr::Call(call),
+                            prop: MemberProp::Ident(prop),
+                            ..
+                        })),
+                    ..
+                } if prop.sym == member && is_require_of(call, source) => Some(local.id.clone()),
+                _ => None,
+            })
+    })
+}
+
+fn is_require_of(call: &CallExpr, source: &str) -> bool {
+    matches!(
+        (&call.callee, call.args.as_slice()),
+        (
+            Callee::Expr(box Expr::Ident(callee)),
+            [ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(str)),
+            }],
+        ) if callee.sym == "require" && str.value == source
+    )
+}
+
 pub fn add_import(ident: Ident, source: Str, source_member_ident: Option<Ident>) -> ImportDecl {
     ImportDecl {
         span: DUMMY_SP,
@@ -486,7 +1122,7 @@ pub fn add_require(
     ctxt: SyntaxContext,
 ) -> Stmt {
     Stmt::Decl(Decl::Var(Box::new(VarDecl {
-        ctxt: ctxt,
+        ctxt,
         span: DUMMY_SP,
         kind: VarDeclKind::Var,
         declare: false,
@@ -499,11 +1135,11 @@ pub fn add_require(
             }),
             init: {
                 let import_call = Expr::Call(CallExpr {
-                    ctxt: ctxt,
+                    ctxt,
                     span: DUMMY_SP,
                     type_args: None,
                     callee: Callee::Expr(Box::new(Expr::Ident(Ident {
-                        ctxt: ctxt,
+                        ctxt,
                         span: DUMMY_SP,
                         sym: "require".into(),
                         optional: false,
@@ -1,28 +1,49 @@
 use std::{
     fs::{self},
-    io::{stdout, ErrorKind},
-    path::PathBuf,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
+use schemars::{schema_for, JsonSchema};
 use serde::Deserialize;
 use swc_core::{
-    common::comments::SingleThreadedComments,
+    common::{
+        comments::SingleThreadedComments,
+        errors::{EmitterWriter, Handler, HANDLER},
+        Mark,
+    },
     ecma::{
-        transforms::testing::{test_fixture, FixtureTestConfig, Tester},
-        visit::{visit_mut_pass, VisitMutPass},
+        ast::{Pass, Program},
+        transforms::{
+            base::resolver,
+            testing::{test_fixture, FixtureTestConfig, Tester},
+            typescript::strip,
+        },
+        visit::visit_mut_pass,
     },
 };
 use swc_plugin_preact_signals::{
-    options::PreactSignalsPluginOptions, utils::MaybeComponentName, SignalsTransformVisitor,
+    accessors::Accessors, manifest::SignalManifest, options::PreactSignalsPluginOptions,
+    SignalsTransformVisitor,
 };
 
 #[cfg(test)]
-fn get_syntax() -> swc_core::ecma::parser::Syntax {
-    use swc_core::ecma::parser::{EsSyntax, Syntax};
+fn get_syntax(is_tsx: bool) -> swc_core::ecma::parser::Syntax {
+    use swc_core::ecma::parser::{EsSyntax, Syntax, TsSyntax};
 
-    let mut es_syntax = EsSyntax::default();
-    es_syntax.jsx = true;
-    Syntax::Es(es_syntax)
+    if is_tsx {
+        Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            explicit_resource_management: true,
+            ..Default::default()
+        })
+    }
 }
 
 trait FlatResult {
@@ -51,13 +72,112 @@ where
     }
 }
 
-#[derive(Debug, Deserialize, Default)]
+/// `options.json` of a fixture
+#[derive(Debug, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields)]
 struct Options {
+    #[serde(rename = "$schema", default)]
+    #[allow(dead_code)]
+    schema: Option<String>,
+    /// file path passed to the plugin, used to detect default exported components
     file_name: Option<String>,
+    /// input is parsed as a script instead of a module
+    #[serde(default)]
+    script: bool,
     #[serde(default)]
     options: PreactSignalsPluginOptions,
 }
 
+const SCHEMA_PATH: &str = "./options.schema.json";
+
+/// `options.schema.json` is generated from the option types, `UPDATE=1` rewrites it
+fn check_schema() -> Result<serde_json::Value, String> {
+    let schema = serde_json::to_value(schema_for!(Options)).unwrap();
+    let generated = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+    let existing = fs::read_to_string(SCHEMA_PATH).unwrap_or_default();
+    if generated != existing {
+        if std::env::var("UPDATE").is_ok_and(|it| it == "1") {
+            fs::write(SCHEMA_PATH, generated).unwrap();
+        } else {
+            return Err(format!(
+                "'{SCHEMA_PATH}' is outdated, run tests with UPDATE=1 to regenerate it"
+            ));
+        }
+    }
+    Ok(schema)
+}
+
+/// invalid configs with a suggestion expected in the error
+const INVALID_OPTIONS: &[(&str, Option<&str>)] = &[
+    (r#"{ "importsource": "signals" }"#, Some("importSource")),
+    (
+        r#"{ "experimental": { "addHookUsageFlags": true } }"#,
+        Some("addHookUsageFlag"),
+    ),
+    (r#"{ "transformHook": false }"#, Some("transformHooks")),
+    (r#"{ "foo": true }"#, None),
+    (r#"{ "mode": "automatic" }"#, None),
+    (r#"{ "mode": "atuo" }"#, Some("auto")),
+    (r#"{ "hocs": [{ "callee": "memo", "arg": 1 }] }"#, None),
+    (
+        r#"{ "hocs": ["memo", { "calee": "connect" }] }"#,
+        Some("callee"),
+    ),
+    (r#"{ "hocs": "reakt" }"#, Some("react")),
+    (
+        r#"{ "wrapStyle": { "component": "using" } }"#,
+        Some("components"),
+    ),
+    (
+        r#"{ "wrapStyle": { "hooks": "try-finaly" } }"#,
+        Some("try-finally"),
+    ),
+    (r#"{ "runtime": "preact" }"#, None),
+    (r#"{ "trackName": ["Icon*"] }"#, Some("trackNames")),
+    (
+        r#"{ "mode": { "components": "all", "hook": "manual" } }"#,
+        Some("hooks"),
+    ),
+    (
+        r#"{ "runtime": { "source": "runtime", "finsh": "end" } }"#,
+        Some("finish"),
+    ),
+    (r#"{ "runtime": { "soruce": "runtime" } }"#, Some("source")),
+    (
+        r#"{ "fileNaming": { "routeFile": ["page"] } }"#,
+        Some("routeFiles"),
+    ),
+    (r#"{ "signalManifest": { "file": "manifest.json" } }"#, None),
+    (
+        r#"{ "rsc": { "clientFile": ["src/**"] } }"#,
+        Some("clientFiles"),
+    ),
+    (
+        r#"{ "signalAccessors": { "method": ["get"] } }"#,
+        Some("methods"),
+    ),
+];
+
+fn check_invalid_options(schema: &serde_json::Value) -> Result<(), String> {
+    let validator = jsonschema::validator_for(schema).map_err(|it| it.to_string())?;
+    for (json, suggestion) in INVALID_OPTIONS {
+        let instance = serde_json::json!({ "options": serde_json::from_str::<serde_json::Value>(json).unwrap() });
+        if validator.is_valid(&instance) {
+            return Err(format!("schema accepts invalid options {json}"));
+        }
+        let error = match PreactSignalsPluginOptions::from_json(json) {
+            Ok(_) => return Err(format!("invalid options {json} are parsed")),
+            Err(error) => error.to_string(),
+        };
+        if let Some(suggestion) =
+            suggestion.filter(|it| !error.contains(&format!("did you mean `{it}`?")))
+        {
+            return Err(format!("no `{suggestion}` suggestion for {json}: {error}"));
+        }
+    }
+    Ok(())
+}
+
 fn read_file_if_exist(
     path: &mut PathBuf,
     suffix: &'static str,
@@ -79,12 +199,96 @@ fn read_file_if_exist(
     res
 }
 
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Handler of `test_fixture` drops warnings, so diagnostics are collected separately
+struct CollectDiagnostics<P> {
+    pass: P,
+    handler: Handler,
+}
+
+impl<P: Pass> Pass for CollectDiagnostics<P> {
+    fn process(&mut self, program: &mut Program) {
+        HANDLER.set(&self.handler, || self.pass.process(program))
+    }
+}
+
+fn compare_diagnostics(path: PathBuf, actual: &Buffer) -> Result<(), String> {
+    let actual = String::from_utf8(actual.0.lock().unwrap().clone()).unwrap();
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    if actual == expected {
+        return Ok(());
+    }
+    if std::env::var("UPDATE").is_ok_and(|it| it == "1") {
+        if actual.is_empty() {
+            fs::remove_file(&path).unwrap();
+        } else {
+            fs::write(&path, actual).unwrap();
+        }
+        return Ok(());
+    }
+    Err(format!(
+        "diagnostics don't match '{}':\n{}",
+        path.display(),
+        actual
+    ))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap().flat_ok().unwrap() {
+        if entry.file_type().unwrap().is_dir() {
+            collect_files(&entry.path(), files);
+        } else {
+            files.push(entry.path());
+        }
+    }
+}
+
+/// fixtures with a `modules` directory check the signal manifest the pre-pass builds from it
+/// against `manifest.json`, `UPDATE=1` rewrites it
+fn check_manifest(dir: &Path, options: &PreactSignalsPluginOptions) -> Result<(), String> {
+    let modules = dir.join("modules");
+    if !modules.is_dir() {
+        return Ok(());
+    }
+    let mut files = vec![];
+    collect_files(&modules, &mut files);
+    let accessors = Accessors::from_options(&options.signal_accessors);
+    let manifest = SignalManifest::build(&files, dir.to_str(), &accessors, options.unknown_origin)
+        .map_err(|it| it.to_string())?
+        .to_json();
+    let path = dir.join("manifest.json");
+    if fs::read_to_string(&path).is_ok_and(|it| it == manifest) {
+        return Ok(());
+    }
+    if std::env::var("UPDATE").is_ok_and(|it| it == "1") {
+        fs::write(&path, manifest).unwrap();
+        return Ok(());
+    }
+    Err(format!(
+        "manifest doesn't match '{}':\n{}",
+        path.display(),
+        manifest
+    ))
+}
+
 fn path_with_suffix(mut path: PathBuf, suffix: &'static str) -> PathBuf {
     path.push(suffix);
     path
 }
 
-fn run_tests() {
+fn run_tests(schema: &serde_json::Value) {
+    let validator = jsonschema::validator_for(schema).expect("schema is valid");
     // we use sync io, for this point of time it's not a problem
     let tests = std::fs::read_dir("./fixtures")
         .expect("fixtures exists")
@@ -104,12 +308,26 @@ fn run_tests() {
         }
 
         let mut dir_path = dir.path();
-        let test_name = dir_path.iter().last().unwrap().to_str().unwrap().to_owned();
+        let test_name = dir_path
+            .iter()
+            .next_back()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned();
 
         let options = read_file_if_exist(&mut dir_path, "options.json");
 
         let plugin_options = match options {
             Ok(Some(content)) => match serde_json::from_str::<Options>(&content) {
+                // parsed options must match the schema
+                Ok(_) if !validator.is_valid(&serde_json::from_str(&content).unwrap()) => {
+                    errors.push(format!(
+                        r#"'options.json' for "{}" doesn't match the schema"#,
+                        test_name
+                    ));
+                    continue;
+                }
                 Ok(opts) => opts,
                 Err(e) => {
                     errors.push(format!(
@@ -119,10 +337,7 @@ fn run_tests() {
                     continue;
                 }
             },
-            Ok(None) => Options {
-                file_name: None,
-                options: PreactSignalsPluginOptions::default(),
-            },
+            Ok(None) => Options::default(),
             Err(e) => {
                 errors.push(format!(
                     r#"Failed to read 'options.json' for "{}": {}"#,
@@ -132,42 +347,78 @@ fn run_tests() {
             }
         };
 
-        use std::io::Write;
-        writeln!(stdout(), "---\ntesting '{}'\n---", test_name).unwrap();
-        let closure =
-            |tester: &mut Tester| -> VisitMutPass<SignalsTransformVisitor<SingleThreadedComments>> {
-                visit_mut_pass(SignalsTransformVisitor::from_options(
-                    plugin_options.options.clone(),
-                    (*tester.comments).clone(),
-                    plugin_options
-                        .file_name
-                        .as_ref()
-                        .map(|it| it.is_trackable())
-                        .unwrap_or(None),
-                ))
-            };
+        println!("---\ntesting '{}'\n---", test_name);
+        // the plugin reads the manifest, so it's checked first
+        if let Err(error) = check_manifest(&dir_path, &plugin_options.options) {
+            errors.push(error);
+            continue;
+        }
+        // fixtures with `in.tsx` are parsed as TypeScript
+        let is_tsx = path_with_suffix(dir_path.clone(), "in.tsx").exists();
+        let (input, output) = if is_tsx {
+            ("in.tsx", "out.tsx")
+        } else {
+            ("in.js", "out.js")
+        };
+
+        let diagnostics = Buffer::default();
+        let closure = |tester: &mut Tester| -> Box<dyn Pass> {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            let emitter = EmitterWriter::new(
+                Box::new(diagnostics.clone()),
+                Some(tester.cm.clone()),
+                false,
+                false,
+            );
+            // swc resolves bindings before running plugins, we should do the same
+            Box::new((
+                resolver(unresolved_mark, top_level_mark, is_tsx),
+                CollectDiagnostics {
+                    pass: visit_mut_pass(
+                        SignalsTransformVisitor::<SingleThreadedComments>::from_options(
+                            plugin_options.options.clone(),
+                            (*tester.comments).clone(),
+                            plugin_options.file_name.as_deref(),
+                        ),
+                    ),
+                    handler: Handler::with_emitter(true, false, Box::new(emitter)),
+                },
+                // swc's `fixer` doesn't restore parens around functions in `as`/`satisfies`,
+                // so types are stripped the same way as in a real build
+                is_tsx.then(|| strip(unresolved_mark, top_level_mark)),
+            ))
+        };
 
         /* I've failed to avoid usage of the 'text_fixture', because low level api is weird */
-        test_fixture::<VisitMutPass<SignalsTransformVisitor<SingleThreadedComments>>>(
-            get_syntax(),
+        test_fixture(
+            get_syntax(is_tsx),
             &closure,
-            &path_with_suffix(dir_path.clone(), "in.js"),
-            &path_with_suffix(dir_path.clone(), "out.js"),
+            &path_with_suffix(dir_path.clone(), input),
+            &path_with_suffix(dir_path.clone(), output),
             FixtureTestConfig {
                 sourcemap: false,
                 allow_error: false,
-                module: Some(true),
+                module: Some(!plugin_options.script),
             },
         );
+        if let Err(error) = compare_diagnostics(
+            path_with_suffix(dir_path.clone(), "out.stderr"),
+            &diagnostics,
+        ) {
+            errors.push(error);
+        }
     }
 
-    if errors.len() > 0 {
+    if !errors.is_empty() {
         panic!("errors:\n {}", errors.join("\n"))
     }
 }
 
 fn main() -> Result<(), String> {
-    run_tests();
+    let schema = check_schema()?;
+    check_invalid_options(&schema)?;
+    run_tests(&schema);
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 import React, { Fragment } from "react";
 import { Signal, batch, signal } from "@preact/signals-core";
-import { useSignals } from "../src/lib/tracking";
+import { trackClassSignals, useSignals } from "../src/lib/tracking";
 import { sleep, tryit } from "radash";
 import {
   describe,
@@ -205,6 +205,117 @@ describe("useSignals", () => {
     expect(child1Spy).toHaveBeenCalledTimes(2);
   });
 
+  it("should rerender class components when signals used in render change", async () => {
+    const renderSpy = vi.fn();
+    const sig = signal(0);
+    class Counter extends React.Component<{ show: boolean }> {
+      componentDidMount() {
+        trackClassSignals.m(this);
+      }
+      componentWillUnmount() {
+        trackClassSignals.u(this);
+      }
+      render() {
+        const store = trackClassSignals(this);
+        try {
+          renderSpy();
+          return <p>{sig.value}</p>;
+        } finally {
+          store.f();
+        }
+      }
+    }
+    function Parent({ show }: { show: boolean }) {
+      return <Fragment>{show && <Counter show={show} />}</Fragment>;
+    }
+
+    await render(<Parent show={true} />);
+    expect(scratch.innerHTML).toBe("<p>0</p>");
+
+    await act(() => {
+      sig.value += 1;
+    });
+    expect(scratch.innerHTML).toBe("<p>1</p>");
+    expect(renderSpy).toHaveBeenCalledTimes(2);
+
+    await render(<Parent show={false} />);
+    await act(() => {
+      sig.value += 1;
+    });
+    expect(renderSpy).toHaveBeenCalledTimes(2);
+  });
+
+  it("should resubscribe class components mounted again in StrictMode", async () => {
+    const sig = signal(0);
+    class Counter extends React.Component {
+      componentDidMount() {
+        trackClassSignals.m(this);
+      }
+      componentWillUnmount() {
+        trackClassSignals.u(this);
+      }
+      render() {
+        const store = trackClassSignals(this);
+        try {
+          return <p>{sig.value}</p>;
+        } finally {
+          store.f();
+        }
+      }
+    }
+
+    // StrictMode unmounts and mounts it again after the first commit, disposing the effect
+    await render(
+      <React.StrictMode>
+        <Counter />
+      </React.StrictMode>,
+    );
+    expect(scratch.innerHTML).toBe("<p>0</p>");
+
+    await act(() => {
+      sig.value += 1;
+    });
+    expect(scratch.innerHTML).toBe("<p>1</p>");
+  });
+
+  it("should subscribe class components only after they are mounted", () => {
+    const sig = signal(0);
+    const forceUpdate = vi.fn();
+    const instance = { forceUpdate };
+    const store = trackClassSignals(instance);
+    try {
+      sig.value;
+    } finally {
+      store.f();
+    }
+
+    // render wasn't committed yet
+    sig.value += 1;
+    expect(forceUpdate).not.toHaveBeenCalled();
+
+    // rerenders for the change made before commit
+    trackClassSignals.m(instance);
+    expect(forceUpdate).toHaveBeenCalledTimes(1);
+    sig.value += 1;
+    expect(forceUpdate).toHaveBeenCalledTimes(2);
+  });
+
+  it("should not throw for class instances without forceUpdate", () => {
+    const sig = signal(0);
+    const instance = {};
+    const store = trackClassSignals(instance);
+    try {
+      sig.value;
+    } finally {
+      store.f();
+    }
+    trackClassSignals.m(instance);
+
+    expect(() => {
+      sig.value += 1;
+    }).not.toThrow();
+  });
+
   it("should not rerender components that only update signals in event handlers", async () => {
     const buttonSpy = vi.fn();
     const AddOneButton = withTrackSignals(